@@ -10,7 +10,7 @@ import {
 import { Program, BN, AnchorProvider } from "@coral-xyz/anchor";
 import IDL from "../../target/stable_coin.json";
 import { Button } from "@/components/ui/button";
-import { TOKEN_METADATA_PROGRAM_ID } from "@/lib/lib";
+import { getProtocolAccounts, TOKEN_METADATA_PROGRAM_ID } from "@/lib/lib";
 import { useWallet } from "@solana/wallet-adapter-react";
 import { Transaction } from "@solana/web3.js";
 
@@ -87,6 +87,12 @@ const Admin = () => {
         new PublicKey(IDL.address)
       );
 
+      const {
+        protocolState,
+        eventAuthority,
+        program: programId,
+      } = getProtocolAccounts(new PublicKey(IDL.address));
+
       addLog(`📍 Mint address: ${mint_address.toString()}`);
       addLog(`📍 Metadata address: ${metadata.toString()}`);
       addLog(`📍 Config address: ${config_address.toString()}`);
@@ -115,7 +121,7 @@ const Admin = () => {
       // Build instruction
       addLog("🔨 Building instruction...");
       const ix = await program.methods
-        .processConfig(new BN(8000), new BN(500), new BN(1), new BN(5000))
+        .processConfig(new BN(8000), new BN(500), new BN(15000), new BN(5000))
         .accounts({
           metadata: metadata,
           tokenMetadataProgram: new PublicKey(TOKEN_METADATA_PROGRAM_ID),
@@ -124,6 +130,9 @@ const Admin = () => {
           ),
           admin: wallet.publicKey,
           sysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+          protocolState,
+          eventAuthority,
+          program: programId,
         })
         .preInstructions([
           ComputeBudgetProgram.setComputeUnitPrice({ microLamports: 100000 }),
@@ -22,7 +22,7 @@ import { useWallet } from "@solana/wallet-adapter-react";
 import { StableCoin } from "../target/stable_coin";
 import { PublicKey } from "@solana/web3.js";
 import { PythSolanaReceiver } from "@pythnetwork/pyth-solana-receiver";
-import { SOL_USDC_FEED_ID } from "@/lib/lib";
+import { getProtocolAccounts, SOL_USDC_FEED_ID } from "@/lib/lib";
 import { Transaction } from "@solana/web3.js";
 import {
   getAssociatedTokenAddress,
@@ -87,6 +87,12 @@ export function BurnTab() {
         [Buffer.from("jacked_nerd")],
         new PublicKey(IDL.address)
       );
+      const {
+        protocolState,
+        treasury,
+        eventAuthority,
+        program: programId,
+      } = getProtocolAccounts(new PublicKey(IDL.address));
       const pyth = new PythSolanaReceiver({
         connection,
         // @ts-ignore
@@ -95,7 +101,7 @@ export function BurnTab() {
       const PRICE_UPDATE = pyth.getPriceFeedAccountAddress(0, SOL_USDC_FEED_ID);
       console.log(amount);
       const ix = await program.methods
-        .withdrawBurn(new BN(amount))
+        .withdrawBurn(new BN(Math.round(amount * 1e9)))
         .accountsPartial({
           mint: mint,
           priceUpdate: PRICE_UPDATE,
@@ -103,6 +109,11 @@ export function BurnTab() {
             "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"
           ),
           withdrawer: wallet.publicKey!,
+          config,
+          protocolState,
+          treasury,
+          eventAuthority,
+          program: programId,
         })
         .instruction();
 
@@ -23,6 +23,7 @@ import IDL from "../target/stable_coin.json";
 import { StableCoin } from "../target/stable_coin";
 import { PythSolanaReceiver } from "@pythnetwork/pyth-solana-receiver";
 import { Transaction } from "@solana/web3.js";
+import { getProtocolAccounts } from "@/lib/lib";
 
 export function DepositTab() {
   const { userState, isLoading, setIsLoading, connection, refetch } =
@@ -71,6 +72,13 @@ export function DepositTab() {
         new PublicKey(IDL.address)
       );
 
+      const {
+        protocolState,
+        treasuryTokenAccount,
+        eventAuthority,
+        program: programId,
+      } = getProtocolAccounts(new PublicKey(IDL.address));
+
       const sol_usdc_feed_id =
         "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d";
 
@@ -91,6 +99,10 @@ export function DepositTab() {
             "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"
           ),
           depositer: wallet.publicKey,
+          protocolState,
+          treasuryTokenAccount,
+          eventAuthority,
+          program: programId,
         })
         .instruction();
 
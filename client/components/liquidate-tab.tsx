@@ -24,11 +24,18 @@ import { LoadingSpinner } from "@/components/loading-spinner";
 import { FeedbackAlert } from "@/components/feedback-alert";
 import { AlertTriangle, Zap, Shield } from "lucide-react";
 import { usePythPrice } from "@/contexts/pythPrice";
-import { LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
-import { BN } from "@coral-xyz/anchor";
+import { LAMPORTS_PER_SOL, PublicKey, Transaction } from "@solana/web3.js";
+import { AnchorProvider, BN } from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
+import { useWallet } from "@solana/wallet-adapter-react";
+import { PythSolanaReceiver } from "@pythnetwork/pyth-solana-receiver";
+import {
+  getAssociatedTokenAddressSync,
+  TOKEN_2022_PROGRAM_ID,
+} from "@solana/spl-token";
 import IDL from "../target/stable_coin.json";
 import { StableCoin } from "../target/stable_coin";
+import { getProtocolAccounts, SOL_USDC_FEED_ID } from "@/lib/lib";
 
 interface CollateralAccount {
   publicKey: PublicKey;
@@ -52,6 +59,7 @@ type AnalyzedCollateral = {
 interface BorrowerAccount {
   id: string;
   walletAddress: string;
+  solAccount: string;
   collateralAmount: number;
   debtAmount: number;
   healthFactor: number;
@@ -83,6 +91,7 @@ function mapToBorrower(
   return {
     id: analyzed.account.publicKey.toBase58(),
     walletAddress: analyzed.account.account.depositer.toBase58(),
+    solAccount: analyzed.account.account.solAccount.toBase58(),
     collateralAmount: collateralSOL,
     debtAmount: debtUSD,
     healthFactor: hf,
@@ -97,6 +106,7 @@ function mapToBorrower(
 export function LiquidateTab() {
   const userStateContext = useUserState();
   const pythPriceContext = usePythPrice();
+  const wallet = useWallet();
 
   const [liquidatingId, setLiquidatingId] = useState<string | null>(null);
   const [borrowers, setBorrowers] = useState<BorrowerAccount[]>([]);
@@ -182,31 +192,82 @@ export function LiquidateTab() {
   }, []); // Empty dependency array - only run once
 
   const handleLiquidate = async (borrower: BorrowerAccount) => {
+    if (!wallet.publicKey) {
+      return;
+    }
     setLiquidatingId(borrower.id);
     setIsLiquidating(true);
     setFeedback(null);
 
     try {
-      // TODO: replace with actual liquidation ix
-      await new Promise((resolve) => setTimeout(resolve, 2500));
-
-      const liquidationBonus = borrower.collateralAmount * 0.05;
-      const liquidatorReward = borrower.collateralAmount + liquidationBonus;
-
-      // Get current balance at time of update
-      const currentBalance = userStateContext.userState.solBalance;
-      userStateContext.updateUserState({
-        solBalance: currentBalance + liquidatorReward,
+      const connection = userStateContext.connection;
+      // @ts-ignore
+      const provider = new AnchorProvider(connection, wallet, {
+        preflightCommitment: "confirmed",
+      });
+      const program: Program<StableCoin> = new Program(IDL, provider);
+      const programId = new PublicKey(IDL.address);
+
+      const [config] = PublicKey.findProgramAddressSync(
+        [Buffer.from("config")],
+        programId
+      );
+      const [mint] = PublicKey.findProgramAddressSync(
+        [Buffer.from("jacked_nerd")],
+        programId
+      );
+      const { protocolState, treasury, eventAuthority } =
+        getProtocolAccounts(programId);
+      const pyth = new PythSolanaReceiver({
+        connection,
+        // @ts-ignore
+        wallet: wallet,
       });
+      const PRICE_UPDATE = pyth.getPriceFeedAccountAddress(0, SOL_USDC_FEED_ID);
+
+      // repay half the debt, the default close factor
+      const coinAmount = new BN(Math.floor(borrower.debtAmount / 2));
+      const ix = await program.methods
+        .liquidate(coinAmount)
+        .accountsPartial({
+          liquidator: wallet.publicKey,
+          collateralAccount: new PublicKey(borrower.id),
+          solAccount: new PublicKey(borrower.solAccount),
+          liquidatorTokenAccount: getAssociatedTokenAddressSync(
+            mint,
+            wallet.publicKey,
+            false,
+            TOKEN_2022_PROGRAM_ID
+          ),
+          config,
+          protocolState,
+          mint,
+          treasury,
+          priceUpdate: PRICE_UPDATE,
+          tokenProgram2022: TOKEN_2022_PROGRAM_ID,
+          eventAuthority,
+          program: programId,
+        })
+        .instruction();
+
+      const bx = await connection.getLatestBlockhash();
+      const tx = new Transaction({
+        feePayer: wallet.publicKey,
+        blockhash: bx.blockhash,
+        lastValidBlockHeight: bx.lastValidBlockHeight,
+      }).add(ix);
+      const txSig = await wallet.sendTransaction(tx, connection);
+      await connection.confirmTransaction(txSig);
 
       setBorrowers((prev) => prev.filter((b) => b.id !== borrower.id));
+      userStateContext.refetch();
 
       setFeedback({
         type: "success",
         message: `Successfully liquidated ${borrower.walletAddress.slice(
           0,
           8
-        )}... and earned ${liquidatorReward.toFixed(4)} SOL (5% bonus)!`,
+        )}... and earned the 5% bonus on the seized SOL!`,
       });
     } catch (error) {
       setFeedback({
@@ -1,3 +1,5 @@
+import { PublicKey } from "@solana/web3.js";
+
 // let (metadata_pda, _bump) = Pubkey::find_program_address(
 //     &[
 //         b"metadata",
@@ -12,3 +14,17 @@ export const TOKEN_METADATA_PROGRAM_ID =
 
 export const SOL_USDC_FEED_ID =
   "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d";
+
+// PDAs most instructions take on top of `config` and `mint`. `eventAuthority`
+// and `program` are the accounts `#[event_cpi]` needs to emit events.
+export function getProtocolAccounts(programId: PublicKey) {
+  const pda = (seed: string) =>
+    PublicKey.findProgramAddressSync([Buffer.from(seed)], programId)[0];
+  return {
+    protocolState: pda("protocol_state"),
+    treasury: pda("treasury"),
+    treasuryTokenAccount: pda("treasury_token_account"),
+    eventAuthority: pda("__event_authority"),
+    program: programId,
+  };
+}
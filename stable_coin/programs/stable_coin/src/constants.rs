@@ -9,9 +9,156 @@ pub const MINTSEED: &[u8] = b"jacked_nerd";
 #[constant]
 pub const COLLATERALSEED: &[u8] = b"collateral";
 
+#[constant]
+pub const COLLATERAL_VAULT_SEED: &[u8] = b"collateral_token_account";
+
+#[constant]
+pub const COLLATERAL_TYPE_SEED: &[u8] = b"collateral_type";
+
+#[constant]
+pub const COLLATERAL_TYPE_VAULT_SEED: &[u8] = b"collateral_vault";
+
+#[constant]
+pub const POSITION_SEED: &[u8] = b"position";
+
+#[constant]
+pub const POSITION_VAULT_SEED: &[u8] = b"position_sol_account";
+
+// max number of SPL collateral types a single position can hold
+#[constant]
+pub const MAX_POSITION_ASSETS: u8 = 4;
+
+#[constant]
+pub const TREASURY_SEED: &[u8] = b"treasury";
+
+#[constant]
+pub const TREASURY_TOKEN_ACCOUNT_SEED: &[u8] = b"treasury_token_account";
+
+// treasury owned token account per collateral mint, holding the liquidation fees of that mint
+#[constant]
+pub const TREASURY_COLLATERAL_SEED: &[u8] = b"treasury_collateral";
+
+#[constant]
+pub const PROTOCOL_STATE_SEED: &[u8] = b"protocol_state";
+
+#[constant]
+pub const AUCTION_SEED: &[u8] = b"auction";
+
+#[constant]
+pub const STABILITY_POOL_SEED: &[u8] = b"stability_pool";
+
+#[constant]
+pub const STABILITY_POOL_TOKEN_ACCOUNT_SEED: &[u8] = b"stability_pool_token_account";
+
+#[constant]
+pub const POOL_DEPOSIT_SEED: &[u8] = b"pool_deposit";
+
+#[constant]
+pub const PSM_SEED: &[u8] = b"psm";
+
+#[constant]
+pub const PSM_VAULT_SEED: &[u8] = b"psm_vault";
+
+// the pool product moves to the next scale once it would fall below this factor
+#[constant]
+pub const POOL_SCALE_FACTOR: u128 = 1_000_000_000;
+
+// decimals of the jacked_nerd mint, also the decimals of native SOL
+#[constant]
+pub const STABLE_DECIMALS: u8 = 9;
+
+#[constant]
+pub const BASIS_POINTS: u64 = 10000;
+
+// bits of `Config.paused_flags`, one per user facing instruction
+#[constant]
+pub const PAUSE_DEPOSIT_MINT: u32 = 1 << 0;
+
+#[constant]
+pub const PAUSE_WITHDRAW_BURN: u32 = 1 << 1;
+
+#[constant]
+pub const PAUSE_LIQUIDATE: u32 = 1 << 2;
+
+#[constant]
+pub const PAUSE_REPAY: u32 = 1 << 3;
+
+#[constant]
+pub const PAUSE_WITHDRAW_COLLATERAL: u32 = 1 << 4;
+
+#[constant]
+pub const PAUSE_DEPOSIT_COLLATERAL: u32 = 1 << 5;
+
+#[constant]
+pub const PAUSE_MINT_STABLE: u32 = 1 << 6;
+
+#[constant]
+pub const PAUSE_STABILITY_POOL: u32 = 1 << 7;
+
+#[constant]
+pub const PAUSE_REDEEM: u32 = 1 << 8;
+
+#[constant]
+pub const PAUSE_PSM: u32 = 1 << 9;
+
+#[constant]
+pub const PAUSE_ALL: u32 = PAUSE_DEPOSIT_MINT
+    | PAUSE_WITHDRAW_BURN
+    | PAUSE_LIQUIDATE
+    | PAUSE_REPAY
+    | PAUSE_WITHDRAW_COLLATERAL
+    | PAUSE_DEPOSIT_COLLATERAL
+    | PAUSE_MINT_STABLE
+    | PAUSE_STABILITY_POOL
+    | PAUSE_REDEEM
+    | PAUSE_PSM;
+
+// default feed written to the config at init, can be changed with `update_oracle`
 #[constant]
 pub const SOL_USDC_FEED_ID :&str = "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d";
 
+#[constant]
+pub const DEFAULT_MAX_PRICE_AGE: u64 = 3600;
+
+// reject prices whose confidence interval is wider than 2% of the price
+#[constant]
+pub const DEFAULT_MAX_CONF_BPS: u64 = 200;
+
 #[constant]
 pub const TOKEN_METADATA_PROGRAM_ID: &str =
-    "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s";
\ No newline at end of file
+    "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s";
+
+#[constant]
+pub const SECONDS_PER_YEAR: u64 = 31_536_000;
+
+// upper bound of `Config.stability_fee_rate`, 100% a year as a per second WAD
+#[constant]
+pub const MAX_STABILITY_FEE_RATE: u128 = 31_709_791_983;
+
+// upper bound of the mint, redemption and liquidation fees, in basis points
+#[constant]
+pub const MAX_FEE_BPS: u64 = 1000;
+
+// auctions start 20% above the oracle price
+#[constant]
+pub const DEFAULT_AUCTION_BUFFER_BPS: u64 = 12_000;
+
+// upper bound of `Config.auction_buffer_bps`
+#[constant]
+pub const MAX_AUCTION_BUFFER_BPS: u64 = 20_000;
+
+// seconds for the auction price to decay from its start price to the floor
+#[constant]
+pub const DEFAULT_AUCTION_DURATION: i64 = 3600;
+
+// the auction price stops decaying at half of its start price
+#[constant]
+pub const DEFAULT_AUCTION_FLOOR_BPS: u64 = 5000;
+
+// seconds for the redemption base rate to halve
+#[constant]
+pub const REDEMPTION_HALF_LIFE: i64 = 43_200;
+
+// a redemption of the whole supply raises the base rate by 1 / REDEMPTION_BETA
+#[constant]
+pub const REDEMPTION_BETA: u128 = 2;
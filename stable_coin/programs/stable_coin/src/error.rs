@@ -8,5 +8,98 @@ pub enum ErrorCode {
     HealthFactorError,
     #[msg("Cannot Liquidate more than the close factor")]
     MaxLiquidationAmount,
+    #[msg("Signer is not the protocol authority")]
+    Unauthorized,
+    #[msg("Liquidation threshold must be between 1 and 10000 basis points")]
+    InvalidLiquidationThreshold,
+    #[msg("Liquidation bonus exceeds the headroom left by the liquidation threshold")]
+    InvalidLiquidationBonus,
+    #[msg("Close factor must be between 1 and 10000 basis points")]
+    InvalidCloseFactor,
+    #[msg("Minimum health factor must be at least 10000 basis points")]
+    InvalidMinHealthFactor,
+    #[msg("Signer is not the pending authority")]
+    NotPendingAuthority,
+    #[msg("No authority transfer has been proposed")]
+    NoPendingAuthority,
+    #[msg("New authority cannot be the default public key")]
+    InvalidAuthority,
+    #[msg("This instruction is paused")]
+    ProtocolPaused,
+    #[msg("Unknown pause flag")]
+    InvalidPauseFlags,
+    #[msg("Guardian can only pause instructions, not unpause them")]
+    GuardianCannotUnpause,
+    #[msg("Price update does not belong to the configured feed")]
+    InvalidPriceFeed,
+    #[msg("Max price age must be greater than zero")]
+    InvalidMaxPriceAge,
+    #[msg("Oracle price must be positive and representable")]
+    InvalidPrice,
+    #[msg("Oracle confidence interval is too wide")]
+    PriceTooUncertain,
+    #[msg("Max confidence ratio must be between 1 and 10000 basis points")]
+    InvalidMaxConfidence,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    #[msg("Division by zero")]
+    DivideByZero,
+    #[msg("Amount must be greater than zero")]
+    InvalidAmount,
+    #[msg("Repay amount exceeds the outstanding debt")]
+    RepayExceedsDebt,
+    #[msg("Withdraw amount exceeds the deposited collateral")]
+    InsufficientCollateral,
+    #[msg("Position still has outstanding debt")]
+    PositionHasDebt,
+    #[msg("Minting would exceed the debt ceiling of this collateral type")]
+    CollateralDebtCeilingExceeded,
+    #[msg("Remaining accounts do not match the position's collateral balances")]
+    InvalidRemainingAccounts,
+    #[msg("Position already holds the maximum number of collateral types")]
+    PositionFull,
+    #[msg("Position holds no balance of this collateral type")]
+    CollateralNotInPosition,
+    #[msg("Stability fee rate is above the allowed maximum")]
+    InvalidStabilityFee,
+    #[msg("Fee is above the allowed maximum")]
+    InvalidFee,
+    #[msg("Treasury does not hold enough funds")]
+    InsufficientTreasuryBalance,
+    #[msg("Minting would exceed the global debt ceiling")]
+    GlobalDebtCeilingExceeded,
+    #[msg("Minting would exceed the max debt of a single position")]
+    PositionDebtCeilingExceeded,
+    #[msg("Position is being auctioned")]
+    PositionInAuction,
+    #[msg("Auction is still running and cannot be restarted")]
+    AuctionActive,
+    #[msg("Auction price is above the bidder's limit")]
+    AuctionPriceAboveLimit,
+    #[msg("Auction buffer, duration or floor is out of range")]
+    InvalidAuctionParams,
+    #[msg("Stability pool does not hold enough stablecoins")]
+    InsufficientPoolDeposits,
+    #[msg("Position still holds collateral")]
+    PositionHasCollateral,
+    #[msg("Position has no debt to write off")]
+    NoBadDebt,
+    #[msg("Redemption positions are not sorted by ascending health factor")]
+    RedemptionNotSorted,
+    #[msg("Redemption positions do not hold enough debt to redeem the amount")]
+    InsufficientRedeemableDebt,
+    #[msg("Redemption fee is above the caller's limit")]
+    RedemptionFeeAboveLimit,
+    #[msg("Swap would exceed the PSM debt ceiling")]
+    PsmDebtCeilingExceeded,
+    #[msg("PSM reserve does not cover the swap")]
+    InsufficientPsmReserve,
+    #[msg("Authority cannot be renounced while instructions are paused")]
+    RenounceWhilePaused,
+    #[msg("Pass exactly one position, and its collateral type for a token position")]
+    InvalidBadDebtPosition,
+    #[msg("Socializing bad debt needs the stability pool accounts")]
+    MissingStabilityPool,
+    #[msg("Position has no debt, is liquidatable or is being auctioned")]
+    PositionNotRedeemable,
 }
-
@@ -0,0 +1,323 @@
+use anchor_lang::prelude::*;
+
+use crate::state::PriceMode;
+
+// emitted when the config is created and whenever governance retunes its risk parameters
+#[event]
+pub struct ConfigUpdated {
+    pub authority:Pubkey,
+    pub old_liq_thx:u64,
+    pub new_liq_thx:u64,
+    pub old_liq_bonus:u64,
+    pub new_liq_bonus:u64,
+    pub old_min_health_factor:u64,
+    pub new_min_health_factor:u64,
+    pub old_close_factor:u64,
+    pub new_close_factor:u64,
+}
+
+#[event]
+pub struct AuthorityProposed {
+    pub authority:Pubkey,
+    pub pending_authority:Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub old_authority:Pubkey,
+    pub new_authority:Pubkey,
+}
+
+// after this event the config can no longer be changed by anyone
+#[event]
+pub struct AuthorityRenounced {
+    pub old_authority:Pubkey,
+}
+
+#[event]
+pub struct GuardianUpdated {
+    pub old_guardian:Option<Pubkey>,
+    pub new_guardian:Option<Pubkey>,
+}
+
+#[event]
+pub struct PauseUpdated {
+    pub signer:Pubkey,
+    pub old_paused_flags:u32,
+    pub new_paused_flags:u32,
+}
+
+#[event]
+pub struct OracleUpdated {
+    pub authority:Pubkey,
+    pub old_feed_id:[u8;32],
+    pub new_feed_id:[u8;32],
+    pub old_max_price_age:u64,
+    pub new_max_price_age:u64,
+    pub old_max_conf_bps:u64,
+    pub new_max_conf_bps:u64,
+    pub old_price_mode:PriceMode,
+    pub new_price_mode:PriceMode,
+}
+
+#[event]
+pub struct StabilityFeeUpdated {
+    pub authority:Pubkey,
+    pub old_stability_fee_rate:u128,
+    pub new_stability_fee_rate:u128,
+    pub debt_index:u128,
+}
+
+#[event]
+pub struct FeesUpdated {
+    pub authority:Pubkey,
+    pub old_mint_fee_bps:u64,
+    pub new_mint_fee_bps:u64,
+    pub old_redemption_fee_bps:u64,
+    pub new_redemption_fee_bps:u64,
+    pub old_liquidation_fee_bps:u64,
+    pub new_liquidation_fee_bps:u64,
+}
+
+#[event]
+pub struct TreasuryWithdrawn {
+    pub authority:Pubkey,
+    pub recipient:Pubkey,
+    pub lamports:u64,
+    pub coins:u64,
+}
+
+#[event]
+pub struct TreasuryCollateralWithdrawn {
+    pub authority:Pubkey,
+    pub recipient:Pubkey,
+    pub mint:Pubkey,
+    pub amount:u64,
+}
+
+#[event]
+pub struct DebtCeilingsUpdated {
+    pub authority:Pubkey,
+    pub old_global_debt_ceiling:u64,
+    pub new_global_debt_ceiling:u64,
+    pub old_max_debt_per_position:u64,
+    pub new_max_debt_per_position:u64,
+}
+
+#[event]
+pub struct PsmParamsUpdated {
+    pub authority:Pubkey,
+    pub old_fee_in_bps:u64,
+    pub new_fee_in_bps:u64,
+    pub old_fee_out_bps:u64,
+    pub new_fee_out_bps:u64,
+    pub old_debt_ceiling:u64,
+    pub new_debt_ceiling:u64,
+}
+
+#[event]
+pub struct AuctionParamsUpdated {
+    pub authority:Pubkey,
+    pub old_auction_buffer_bps:u64,
+    pub new_auction_buffer_bps:u64,
+    pub old_auction_duration:i64,
+    pub new_auction_duration:i64,
+    pub old_auction_floor_bps:u64,
+    pub new_auction_floor_bps:u64,
+}
+
+// User facing events. `collateral_type` is `None` for native SOL, prices are WAD
+// and health factors are the WAD value left after the instruction.
+
+#[event]
+pub struct PositionOpened {
+    pub owner:Pubkey,
+    pub position:Pubkey,
+    pub collateral_type:Option<Pubkey>,
+}
+
+#[event]
+pub struct PositionClosed {
+    pub owner:Pubkey,
+    pub position:Pubkey,
+    pub lamports:u64,
+}
+
+#[event]
+pub struct CollateralDeposited {
+    pub owner:Pubkey,
+    pub position:Pubkey,
+    pub collateral_type:Option<Pubkey>,
+    pub amount:u64,
+}
+
+#[event]
+pub struct Minted {
+    pub owner:Pubkey,
+    pub position:Pubkey,
+    pub amount:u64,
+    // part of `amount` minted to the treasury
+    pub fee:u64,
+    // stablecoins owed by the position after the mint
+    pub debt:u64,
+    // `None` when the position is valued with several prices
+    pub price:Option<u128>,
+    pub health_factor:u128,
+}
+
+// repaying does not read the oracle, so only the remaining debt is reported
+#[event]
+pub struct Repaid {
+    pub payer:Pubkey,
+    pub position:Pubkey,
+    pub amount:u64,
+    pub debt:u64,
+}
+
+#[event]
+pub struct CollateralWithdrawn {
+    pub owner:Pubkey,
+    pub position:Pubkey,
+    pub collateral_type:Option<Pubkey>,
+    pub amount:u64,
+    // part of `amount` paid to the treasury
+    pub fee:u64,
+    pub price:u128,
+    pub health_factor:u128,
+}
+
+#[event]
+pub struct Liquidated {
+    pub liquidator:Pubkey,
+    pub position:Pubkey,
+    pub collateral_type:Option<Pubkey>,
+    // stablecoins burned by the liquidator
+    pub repaid:u64,
+    // collateral sent to the liquidator, bonus included
+    pub seized:u64,
+    // collateral sent to the treasury
+    pub fee:u64,
+    pub price:u128,
+    pub health_factor:u128,
+}
+
+#[event]
+pub struct CollateralTypeAdded {
+    pub authority:Pubkey,
+    pub collateral_type:Pubkey,
+    pub mint:Pubkey,
+    pub feed_id:[u8;32],
+    pub liq_thx:u64,
+    pub liq_bonus:u64,
+    pub debt_ceiling:u64,
+}
+
+#[event]
+pub struct TreasuryInitialized {
+    pub authority:Pubkey,
+    pub treasury:Pubkey,
+    pub token_account:Pubkey,
+}
+
+#[event]
+pub struct AuctionStarted {
+    pub keeper:Pubkey,
+    pub position:Pubkey,
+    pub auction:Pubkey,
+    pub debt:u64,
+    pub lamports:u64,
+    pub start_price:u128,
+}
+
+#[event]
+pub struct AuctionBid {
+    pub bidder:Pubkey,
+    pub position:Pubkey,
+    pub auction:Pubkey,
+    // collateral bought
+    pub lamports:u64,
+    // stablecoins burned by the bidder
+    pub cost:u64,
+    // lamports taken from the position for the treasury on top of the lot
+    pub fee:u64,
+    pub price:u128,
+}
+
+// emitted once the debt is covered or the collateral runs out
+#[event]
+pub struct AuctionSettled {
+    pub position:Pubkey,
+    pub auction:Pubkey,
+    // collateral returned to the borrower
+    pub surplus:u64,
+    // debt left on the position, either not covered by the auction or owed by a position that is healthy again
+    pub remaining_debt:u64,
+}
+
+#[event]
+pub struct StabilityPoolInitialized {
+    pub authority:Pubkey,
+    pub pool:Pubkey,
+    pub token_account:Pubkey,
+}
+
+// `deposit` is what the depositor holds in the pool afterwards, `collateral_gain` the
+// lamports paid out to them from earlier liquidations
+#[event]
+pub struct StabilityPoolDeposited {
+    pub depositor:Pubkey,
+    pub amount:u64,
+    pub deposit:u64,
+    pub collateral_gain:u64,
+}
+
+#[event]
+pub struct StabilityPoolWithdrawn {
+    pub depositor:Pubkey,
+    pub amount:u64,
+    pub deposit:u64,
+    pub collateral_gain:u64,
+}
+
+// `covered` was burned from the treasury, `socialized` from the stability pool deposits and
+// `bad_debt` is the protocol's uncovered bad debt afterwards
+#[event]
+pub struct BadDebtSettled {
+    pub authority:Pubkey,
+    pub position:Pubkey,
+    pub debt:u64,
+    pub covered:u64,
+    pub socialized:u64,
+    pub bad_debt:u64,
+}
+
+// `lamports` is the collateral taken from the positions, `fee` the part of it paid to the treasury
+#[event]
+pub struct Redeemed {
+    pub redeemer:Pubkey,
+    pub amount:u64,
+    pub lamports:u64,
+    pub fee:u64,
+    pub fee_rate:u128,
+    pub price:u128,
+    pub positions:u8,
+}
+
+#[event]
+pub struct PsmInitialized {
+    pub authority:Pubkey,
+    pub psm:Pubkey,
+    pub reserve_mint:Pubkey,
+    pub vault:Pubkey,
+}
+
+// `reserve` is in base units of the reserve mint, `coins` and `fee` in stablecoin base units
+#[event]
+pub struct PsmSwapped {
+    pub user:Pubkey,
+    // true for `swap_in`, false for `swap_out`
+    pub swap_in:bool,
+    pub reserve:u64,
+    pub coins:u64,
+    pub fee:u64,
+}
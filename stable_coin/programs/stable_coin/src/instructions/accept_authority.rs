@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, events::AuthorityTransferred, state::Config};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    pub pending_authority:Signer<'info>,
+    #[account(
+        mut,
+        seeds=[b"config"],
+        bump=config.bump,
+    )]
+    pub config:Account<'info,Config>,
+}
+
+pub fn process_accept_authority(ctx:Context<AcceptAuthority>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let pending_authority = config.pending_authority.ok_or(ErrorCode::NoPendingAuthority)?;
+
+    require_keys_eq!(
+        pending_authority,
+        ctx.accounts.pending_authority.key(),
+        ErrorCode::NotPendingAuthority
+    );
+
+    let old_authority = config.authority;
+    config.authority = pending_authority;
+    config.pending_authority = None;
+
+    emit_cpi!(AuthorityTransferred {
+        old_authority,
+        new_authority: pending_authority,
+    });
+    Ok(())
+}
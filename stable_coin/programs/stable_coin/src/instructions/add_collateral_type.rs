@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{error::ErrorCode, events::CollateralTypeAdded, state::{CollateralType, Config, Treasury}, validate_liquidation_params};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AddCollateralType<'info> {
+    #[account(mut)]
+    pub authority:Signer<'info>,
+    #[account(
+        seeds=[b"config"],
+        bump=config.bump,
+        has_one=authority @ ErrorCode::Unauthorized
+    )]
+    pub config:Account<'info,Config>,
+    #[account(
+        mint::token_program=collateral_token_program
+    )]
+    pub collateral_mint:InterfaceAccount<'info,Mint>,
+    #[account(
+        init,
+        payer=authority,
+        seeds=[b"collateral_type", collateral_mint.key().as_ref()],
+        space= 8 + CollateralType::INIT_SPACE,
+        bump
+    )]
+    pub collateral_type:Account<'info,CollateralType>,
+    #[account(
+        init,
+        payer=authority,
+        seeds=[b"collateral_vault", collateral_mint.key().as_ref()],
+        token::mint=collateral_mint,
+        token::authority=collateral_type,
+        token::token_program=collateral_token_program,
+        bump
+    )]
+    pub vault:InterfaceAccount<'info,TokenAccount>,
+    #[account(
+        seeds=[b"treasury"],
+        bump=treasury.bump
+    )]
+    pub treasury:Account<'info,Treasury>,
+    #[account(
+        init,
+        payer=authority,
+        seeds=[b"treasury_collateral", collateral_mint.key().as_ref()],
+        token::mint=collateral_mint,
+        token::authority=treasury,
+        token::token_program=collateral_token_program,
+        bump
+    )]
+    pub treasury_collateral_account:InterfaceAccount<'info,TokenAccount>,
+    pub collateral_token_program:Interface<'info,TokenInterface>,
+    pub system_program:Program<'info,System>,
+}
+
+// 1. Validate the risk parameters of the new collateral
+// 2. Store them on the collateral type, next to the vault that will hold the deposits
+//    and the treasury account its liquidation fees are paid into
+
+pub fn process_add_collateral_type(
+    ctx:Context<AddCollateralType>,
+    feed_id:[u8;32],
+    liq_thx:u64,
+    liq_bonus:u64,
+    debt_ceiling:u64,
+) -> Result<()> {
+    // 1. validate the risk parameters
+    validate_liquidation_params(liq_thx, liq_bonus)?;
+
+    // 2. store the collateral type
+    ctx.accounts.collateral_type.set_inner(CollateralType {
+        mint: ctx.accounts.collateral_mint.key(),
+        vault: ctx.accounts.vault.key(),
+        feed_id,
+        decimals: ctx.accounts.collateral_mint.decimals,
+        liq_thx,
+        liq_bonus,
+        debt_ceiling,
+        total_debt: 0,
+        bump: ctx.bumps.collateral_type,
+        bump_vault: ctx.bumps.vault,
+    });
+
+    emit_cpi!(CollateralTypeAdded {
+        authority: ctx.accounts.authority.key(),
+        collateral_type: ctx.accounts.collateral_type.key(),
+        mint: ctx.accounts.collateral_mint.key(),
+        feed_id,
+        liq_thx,
+        liq_bonus,
+        debt_ceiling,
+    });
+    Ok(())
+}
@@ -0,0 +1,182 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface}};
+
+use crate::{auction_price, burn_tokens, close_auction, error::ErrorCode, events::{AuctionBid, AuctionSettled}, fee_amount, lamports_to_usd, math::Rounding, state::{Auction, Collateral, Config, ProtocolState, Treasury}, transfer_from_vault, usd_to_lamports, PAUSE_LIQUIDATE};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct BidAuction<'info>{
+    #[account(mut)]
+    pub bidder:Signer<'info>,
+    #[account(
+        mut,
+        associated_token::mint=mint,
+        associated_token::authority=bidder,
+        associated_token::token_program=token_program_2022
+    )]
+    pub bidder_token_account:InterfaceAccount<'info,TokenAccount>,
+    #[account(
+        mut,
+        seeds=[b"collateral", collateral_account.depositer.as_ref()],
+        bump=collateral_account.bump,
+        has_one=sol_account
+    )]
+    pub collateral_account:Account<'info,Collateral>,
+    /// SAFETY: This account is only used as a source for SOL transfers.
+    /// `has_one` on the collateral account ties it to the auctioned position.
+    #[account(mut)]
+    pub sol_account:AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds=[b"auction", collateral_account.key().as_ref()],
+        bump=auction.bump,
+        has_one=owner,
+        has_one=keeper
+    )]
+    pub auction:Account<'info,Auction>,
+    /// SAFETY: borrower of the auctioned position, only receives the surplus collateral.
+    #[account(mut)]
+    pub owner:AccountInfo<'info>,
+    /// SAFETY: opener of the auction, only receives the auction account's rent.
+    #[account(mut)]
+    pub keeper:AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds=[b"treasury"],
+        bump=treasury.bump
+    )]
+    pub treasury:Account<'info,Treasury>,
+    #[account(
+        mut,
+        seeds=[b"config"],
+        bump=config.bump
+    )]
+    pub config:Account<'info,Config>,
+    #[account(
+        mut,
+        seeds=[b"protocol_state"],
+        bump=protocol_state.bump
+    )]
+    pub protocol_state:Account<'info,ProtocolState>,
+    #[account(
+        mut,
+        seeds=[b"jacked_nerd"],
+        mint::authority=mint,
+        mint::freeze_authority=mint,
+        mint::token_program=token_program_2022,
+        bump
+    )]
+    pub mint:InterfaceAccount<'info,Mint>,
+    pub system_program:Program<'info,System>,
+    pub token_program_2022:Interface<'info,TokenInterface>,
+    pub associated_token_program:Program<'info,AssociatedToken>
+}
+
+//  1. Price the lot at the current auction price, rejecting it above the bidder's limit
+//  2. Only sell as much collateral as the remaining debt pays for
+//  3. Burn the bidder's stablecoins against the position's debt, send them the collateral
+//     and the liquidation fee on it to the treasury
+//  4. Once the debt is covered or the collateral is gone, return the surplus to the
+//     borrower, unlock the position and close the auction
+
+pub fn process_bid_auction(ctx:Context<BidAuction>, lamports:u64, max_price:u128) -> Result<()> {
+    require!(!ctx.accounts.config.is_paused(PAUSE_LIQUIDATE), ErrorCode::ProtocolPaused);
+    require!(lamports > 0, ErrorCode::InvalidAmount);
+
+    let collateral = &mut ctx.accounts.collateral_account;
+    let config = &mut ctx.accounts.config;
+    config.accrue_interest()?;
+
+    // 1. current auction price
+    let elapsed = Clock::get()?.unix_timestamp.saturating_sub(ctx.accounts.auction.started_at);
+    let price = auction_price(ctx.accounts.auction.start_price, elapsed, config.auction_duration, config.auction_floor_bps)?;
+    require!(price <= max_price, ErrorCode::AuctionPriceAboveLimit);
+
+    // 2. a lot worth more than the debt is cut down to the lamports that cover it
+    let debt = config.debt_of(collateral.coins)?;
+    let lot = lamports.min(collateral.lamports);
+    let cost = lamports_to_usd(lot, price, Rounding::Up)?;
+    let (lot, cost) = if cost >= debt {
+        (usd_to_lamports(debt, price, Rounding::Up)?.min(lot), debt)
+    } else {
+        (lot, cost)
+    };
+    let repaid_coins = config.normalized_repayment(collateral.coins, cost)?;
+    // the treasury's cut is taken from the borrower on top of the lot, out of what is left
+    let fee = fee_amount(lot, config.liquidation_fee_bps)?.min(collateral.lamports - lot);
+
+    // 3. settle the lot
+    burn_tokens(
+        &ctx.accounts.mint,
+        &ctx.accounts.token_program_2022,
+        &ctx.accounts.bidder_token_account,
+        cost,
+        &ctx.accounts.bidder
+    )?;
+    transfer_from_vault(
+        &ctx.accounts.system_program,
+        &ctx.accounts.sol_account,
+        &ctx.accounts.bidder.to_account_info(),
+        &collateral.depositer,
+        collateral.bump_sol_account,
+        lot
+    )?;
+    if fee > 0 {
+        transfer_from_vault(
+            &ctx.accounts.system_program,
+            &ctx.accounts.sol_account,
+            &ctx.accounts.treasury.to_account_info(),
+            &collateral.depositer,
+            collateral.bump_sol_account,
+            fee
+        )?;
+    }
+    let taken = lot.checked_add(fee).ok_or(ErrorCode::MathOverflow)?;
+
+    collateral.coins = collateral.coins.checked_sub(repaid_coins).ok_or(ErrorCode::MathOverflow)?;
+    collateral.lamports = collateral.lamports.checked_sub(taken).ok_or(ErrorCode::MathOverflow)?;
+    config.remove_debt(repaid_coins)?;
+
+    let protocol_state = &mut ctx.accounts.protocol_state;
+    protocol_state.remove_collateral(taken)?;
+    protocol_state.record_liquidation(cost)?;
+    protocol_state.record_fee(lamports_to_usd(fee, price, Rounding::Down)?)?;
+
+    emit_cpi!(AuctionBid {
+        bidder: ctx.accounts.bidder.key(),
+        position: collateral.key(),
+        auction: ctx.accounts.auction.key(),
+        lamports: lot,
+        cost,
+        fee,
+        price,
+    });
+
+    // 4. settle the auction once there is nothing left to sell or to cover
+    if collateral.coins == 0 || collateral.lamports == 0 {
+        let surplus = if collateral.coins == 0 { collateral.lamports } else { 0 };
+        if surplus > 0 {
+            transfer_from_vault(
+                &ctx.accounts.system_program,
+                &ctx.accounts.sol_account,
+                &ctx.accounts.owner,
+                &collateral.depositer,
+                collateral.bump_sol_account,
+                surplus
+            )?;
+            collateral.lamports = 0;
+            protocol_state.remove_collateral(surplus)?;
+        }
+
+        emit_cpi!(AuctionSettled {
+            position: collateral.key(),
+            auction: ctx.accounts.auction.key(),
+            surplus,
+            remaining_debt: config.debt_of(collateral.coins)?,
+        });
+        close_auction(collateral, &ctx.accounts.auction, &ctx.accounts.keeper)?;
+    }
+
+    protocol_state.sync_debt(config)?;
+    Ok(())
+}
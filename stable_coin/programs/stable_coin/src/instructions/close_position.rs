@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, events::PositionClosed, state::{Collateral, ProtocolState}, transfer_from_vault};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClosePosition<'info>{
+    #[account(mut)]
+    pub depositer:Signer<'info>,
+    #[account(
+        mut,
+        seeds=[b"collateral", depositer.key().as_ref()],
+        bump=collateral_account.bump,
+        has_one=depositer,
+        has_one=sol_account,
+        close=depositer
+    )]
+    pub collateral_account:Account<'info,Collateral>,
+    #[account(
+        mut,
+        seeds=[b"protocol_state"],
+        bump=protocol_state.bump
+    )]
+    pub protocol_state:Account<'info,ProtocolState>,
+    /// SAFETY: This account is only used as a source for SOL transfers.
+    /// The seeds ensure that the PDA is derived deterministically and cannot be arbitrarily passed in by the client.
+    #[account(
+        mut,
+        seeds=[b"collateral_token_account",depositer.key().as_ref()],
+        bump=collateral_account.bump_sol_account
+    )]
+    pub sol_account: AccountInfo<'info>,
+    pub system_program:Program<'info, System>,
+}
+
+//  1. Make sure the position has no outstanding debt
+//  2. Sweep every lamport left in the sol account, rent included, back to the user
+//  3. Remove the position from the protocol totals
+//  4. The collateral account itself is closed to the user by the `close` constraint
+
+pub fn process_close_position(ctx:Context<ClosePosition>) -> Result<()> {
+    // 1. only debt free positions can be closed
+    require!(ctx.accounts.collateral_account.coins == 0, ErrorCode::PositionHasDebt);
+    require!(!ctx.accounts.collateral_account.in_auction, ErrorCode::PositionInAuction);
+
+    // 2. empty the sol account, the system program reclaims it once it holds no lamports
+    let vault_lamports = ctx.accounts.sol_account.lamports();
+    if vault_lamports > 0 {
+        transfer_from_vault(
+            &ctx.accounts.system_program,
+            &ctx.accounts.sol_account,
+            &ctx.accounts.depositer.to_account_info(),
+            ctx.accounts.depositer.key,
+            ctx.accounts.collateral_account.bump_sol_account,
+            vault_lamports
+        )?;
+    }
+
+    // 3. drop the position from the protocol totals
+    let protocol_state = &mut ctx.accounts.protocol_state;
+    protocol_state.remove_collateral(ctx.accounts.collateral_account.lamports)?;
+    protocol_state.close_position()?;
+
+    emit_cpi!(PositionClosed {
+        owner: ctx.accounts.depositer.key(),
+        position: ctx.accounts.collateral_account.key(),
+        lamports: vault_lamports,
+    });
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+
+use crate::{error::ErrorCode, events::{CollateralDeposited, PositionOpened}, state::{Collateral, Config, ProtocolState}, PAUSE_DEPOSIT_COLLATERAL};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DepositCollateral<'info> {
+    #[account(mut)]
+    pub depositer:Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer=depositer,
+        seeds=[b"collateral", depositer.key().as_ref()],
+        space= 8 + Collateral::INIT_SPACE,
+        bump
+    )]
+    pub collateral_account:Account<'info,Collateral>,
+    /// SAFETY: This account is only used as a recipient for SOL transfers. 
+    /// The seeds ensure that the PDA is derived deterministically and cannot be arbitrarily passed in by the client.
+    #[account(
+        init_if_needed,
+        payer=depositer,
+        seeds=[b"collateral_token_account",depositer.key().as_ref()],
+        space=0,
+        owner =  System::id(),
+        bump
+    )]
+    pub sol_token_account: AccountInfo<'info>,
+    #[account(
+        seeds=[b"config"],
+        bump=config.bump
+    )]
+    pub config:Account<'info,Config>,
+    #[account(
+        mut,
+        seeds=[b"protocol_state"],
+        bump=protocol_state.bump
+    )]
+    pub protocol_state:Account<'info,ProtocolState>,
+    pub system_program:Program<'info,System>,
+}
+
+    // 1. Checking if this is initial deposit
+    // 2. Save the collateral into the sol account
+    // 3. Updating user state
+    //
+    // Nothing is minted, the deposit only adds a buffer to the position.
+
+pub fn process_deposit_collateral(ctx: Context<DepositCollateral>, lamports:u64) -> Result<()> {
+    require!(!ctx.accounts.config.is_paused(PAUSE_DEPOSIT_COLLATERAL), ErrorCode::ProtocolPaused);
+    require!(lamports > 0, ErrorCode::InvalidAmount);
+
+    // 1. checking if this is initial deposit
+    let collateral = &mut ctx.accounts.collateral_account;
+    let opened = !collateral.is_initialized;
+    if opened {
+        collateral.bump = ctx.bumps.collateral_account;
+        collateral.bump_sol_account = ctx.bumps.sol_token_account;
+        collateral.is_initialized = true;
+        collateral.sol_account = ctx.accounts.sol_token_account.key();
+        collateral.depositer = ctx.accounts.depositer.key();
+        ctx.accounts.protocol_state.open_position()?;
+    }
+
+    // 2. save the collateral into the sol account
+    let context = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),Transfer {
+        from:ctx.accounts.depositer.to_account_info(),
+        to:ctx.accounts.sol_token_account.to_account_info()
+    });
+    transfer(context, lamports)?;
+
+    // 3. updating user state
+    collateral.lamports = collateral.lamports.checked_add(lamports).ok_or(ErrorCode::MathOverflow)?;
+    ctx.accounts.protocol_state.add_collateral(lamports)?;
+
+    let owner = ctx.accounts.depositer.key();
+    let position = collateral.key();
+    if opened {
+        emit_cpi!(PositionOpened { owner, position, collateral_type: None });
+    }
+    emit_cpi!(CollateralDeposited { owner, position, collateral_type: None, amount: lamports });
+    Ok(())
+}
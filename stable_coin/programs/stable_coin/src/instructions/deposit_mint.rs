@@ -1,8 +1,9 @@
 use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
 use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface}};
-use pyth_solana_receiver_sdk::price_update::{get_feed_id_from_hex, PriceUpdateV2};
-use crate::{calculate_health_factor, error::ErrorCode, integer_usd_from_pyth, lamports_to_usd, mint_tokens, state::{Collateral, Config}, SOL_USDC_FEED_ID};
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+use crate::{calculate_health_factor, error::ErrorCode, events::{CollateralDeposited, Minted, PositionOpened}, lamports_to_usd, math::Rounding, mint_with_fee, oracle_price, state::{Collateral, Config, ProtocolState}, PriceBound, PAUSE_DEPOSIT_MINT};
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct InitDeposit<'info> {
     #[account(mut)]
@@ -40,6 +41,12 @@ pub struct InitDeposit<'info> {
         bump
     )]
     pub config:Account<'info,Config>,
+    #[account(
+        mut,
+        seeds=[b"protocol_state"],
+        bump=protocol_state.bump
+    )]
+    pub protocol_state:Account<'info,ProtocolState>,
     #[account(
         mut,
         seeds=[b"jacked_nerd"],
@@ -50,6 +57,12 @@ pub struct InitDeposit<'info> {
     
     )]
     pub mint:InterfaceAccount<'info,Mint>,
+    #[account(
+        mut,
+        seeds=[b"treasury_token_account"],
+        bump
+    )]
+    pub treasury_token_account:InterfaceAccount<'info,TokenAccount>,
     pub system_program:Program<'info,System>,
     pub price_update:Account<'info, PriceUpdateV2>,
     pub token_program_2022: Interface<'info, TokenInterface>,
@@ -63,11 +76,16 @@ pub struct InitDeposit<'info> {
     // 5. Updating user state
 
 pub fn process_deposit(ctx: Context<InitDeposit>,amount:u64) -> Result<()> {
+    require!(!ctx.accounts.config.is_paused(PAUSE_DEPOSIT_MINT), ErrorCode::ProtocolPaused);
+    require!(!ctx.accounts.collateral_account.in_auction, ErrorCode::PositionInAuction);
+
     // 1. checking if this is initial deposit -
     let collateral = &mut ctx.accounts.collateral_account;
     let config = &mut ctx.accounts.config;
     let pyth = &mut ctx.accounts.price_update;
+    config.accrue_interest()?;
 
+    let opened = !collateral.is_initialized;
     if collateral.is_initialized == false {
         collateral.bump = ctx.bumps.collateral_account;
         collateral.bump_sol_account = ctx.bumps.sol_token_account;
@@ -75,6 +93,7 @@ pub fn process_deposit(ctx: Context<InitDeposit>,amount:u64) -> Result<()> {
         collateral.coin_token_account = ctx.accounts.depositer_token_account.key();
         collateral.sol_account = ctx.accounts.sol_token_account.key();
         collateral.depositer = ctx.accounts.depositer.key.key();
+        ctx.accounts.protocol_state.open_position()?;
     }
     
    // 2. save the collateral into associated sol acount
@@ -87,41 +106,60 @@ pub fn process_deposit(ctx: Context<InitDeposit>,amount:u64) -> Result<()> {
    transfer(context, amount)?;
 
    // 3. Get USD equivalent of the provided sol
-   let feed_id = get_feed_id_from_hex(SOL_USDC_FEED_ID)?;
-   let clock = &mut Clock::get()?;
-   let usd =  pyth.get_price_no_older_than(&clock, 3600, &feed_id)?;
-
-   msg!("price price:{}",usd.price);
-   msg!("price exponent:{}",usd.exponent);
-
-   let sol_price = amount.checked_div(1000000000).unwrap();
-   msg!("sol price:{}",sol_price);
   // Fetching sol live price
-   let usd_amount = integer_usd_from_pyth(usd.price, usd.exponent);
-   msg!("usd amount:{}",usd_amount);
-   let token_amt = lamports_to_usd(amount,usd_amount as u64)?;
-   let final_token_amt = token_amt.checked_mul(config.liq_thx).unwrap().checked_div(10000).unwrap();
+   // collateral is valued at the low end of the confidence interval
+   let usd_amount = oracle_price(pyth, config, PriceBound::Lower)?;
+   let token_amt = lamports_to_usd(amount, usd_amount, Rounding::Down)?;
+   // mint as much as keeps the deposit at the configured minimum health factor
+   let final_token_amt = token_amt.checked_mul(config.liq_thx).ok_or(ErrorCode::MathOverflow)?.checked_div(config.min_health_factor).ok_or(ErrorCode::DivideByZero)?;
 
   //4.Checking HF to ensure safety.
-    let new_collateral_amount = collateral.lamports.checked_add(amount).unwrap();
-    let new_collateral_in_usd = lamports_to_usd(new_collateral_amount, usd_amount as u64)?;
+    let new_collateral_amount = collateral.lamports.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+    let new_collateral_in_usd = lamports_to_usd(new_collateral_amount, usd_amount, Rounding::Down)?;
+    let normalized_amount = config.normalize(final_token_amt, Rounding::Up)?;
+    let new_coins_balance = collateral.coins.checked_add(normalized_amount).ok_or(ErrorCode::MathOverflow)?;
+    config.add_debt(normalized_amount, new_coins_balance)?;
 
-   let health_factor = calculate_health_factor(collateral.coins, new_collateral_in_usd, config.liq_thx);
+   let new_debt = config.debt_of(new_coins_balance)?;
+   let health_factor = calculate_health_factor(new_debt, new_collateral_in_usd, config.liq_thx)?;
 
-   if health_factor < 1 {
+   if health_factor < config.min_health_factor_wad()? {
         return Err(ErrorCode::HealthFactorError.into());
    }
-   // 5. mint tokens to the user
-   mint_tokens(
-        &mut ctx.accounts.depositer_token_account,
-         &mut ctx.accounts.token_program_2022,
-        &mut ctx.accounts.mint, 
-        config.bump_mint_acc, 
-        final_token_amt
+   // 5. mint tokens to the user, less the mint fee which goes to the treasury
+   let mint_fee = mint_with_fee(
+        &ctx.accounts.depositer_token_account,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.token_program_2022,
+        &ctx.accounts.mint,
+        config.bump_mint_acc,
+        final_token_amt,
+        config.mint_fee_bps
     )?;
    
-   // 6. Updating user state
-   collateral.coins = collateral.coins.checked_add(final_token_amt).unwrap();
-   collateral.lamports = collateral.lamports.checked_add(amount).unwrap();
+   // 6. Updating user and protocol state
+   collateral.coins = new_coins_balance;
+   collateral.lamports = new_collateral_amount;
+   let protocol_state = &mut ctx.accounts.protocol_state;
+   protocol_state.add_collateral(amount)?;
+   protocol_state.record_fee(mint_fee)?;
+   protocol_state.sync_debt(config)?;
+
+   // 7. emit events for indexers
+   let owner = ctx.accounts.depositer.key();
+   let position = ctx.accounts.collateral_account.key();
+   if opened {
+        emit_cpi!(PositionOpened { owner, position, collateral_type: None });
+   }
+   emit_cpi!(CollateralDeposited { owner, position, collateral_type: None, amount });
+   emit_cpi!(Minted {
+        owner,
+        position,
+        amount: final_token_amt,
+        fee: mint_fee,
+        debt: new_debt,
+        price: Some(usd_amount),
+        health_factor,
+   });
     Ok(())
 }
@@ -0,0 +1,65 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+
+use crate::{error::ErrorCode, events::CollateralDeposited, state::{Config, Position, ProtocolState}, PAUSE_DEPOSIT_COLLATERAL};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DepositPositionSol<'info> {
+    #[account(mut)]
+    pub owner:Signer<'info>,
+    #[account(
+        seeds=[b"config"],
+        bump=config.bump
+    )]
+    pub config:Account<'info,Config>,
+    #[account(
+        mut,
+        seeds=[b"protocol_state"],
+        bump=protocol_state.bump
+    )]
+    pub protocol_state:Account<'info,ProtocolState>,
+    #[account(
+        mut,
+        seeds=[b"position", owner.key().as_ref()],
+        bump=position.bump
+    )]
+    pub position:Account<'info,Position>,
+    /// SAFETY: This account is only used as a recipient for SOL transfers.
+    /// The seeds ensure that the PDA is derived deterministically and cannot be arbitrarily passed in by the client.
+    #[account(
+        mut,
+        seeds=[b"position_sol_account", owner.key().as_ref()],
+        bump=position.bump_sol_account
+    )]
+    pub position_sol_account:AccountInfo<'info>,
+    pub system_program:Program<'info,System>,
+}
+
+//  1. Move the SOL into the position's vault
+//  2. Increase the position's SOL balance
+
+pub fn process_deposit_position_sol(ctx:Context<DepositPositionSol>, amount:u64) -> Result<()> {
+    require!(!ctx.accounts.config.is_paused(PAUSE_DEPOSIT_COLLATERAL), ErrorCode::ProtocolPaused);
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    // 1. move the SOL into the vault
+    let context = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),Transfer {
+        from:ctx.accounts.owner.to_account_info(),
+        to:ctx.accounts.position_sol_account.to_account_info()
+    });
+    transfer(context, amount)?;
+
+    // 2. update the position
+    let position = &mut ctx.accounts.position;
+    position.lamports = position.lamports.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+    ctx.accounts.protocol_state.add_collateral(amount)?;
+
+    emit_cpi!(CollateralDeposited {
+        owner: ctx.accounts.owner.key(),
+        position: position.key(),
+        collateral_type: None,
+        amount,
+    });
+    Ok(())
+}
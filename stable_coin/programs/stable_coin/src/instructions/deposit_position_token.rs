@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{error::ErrorCode, events::CollateralDeposited, state::{CollateralType, Config, Position, PositionBalance}, MAX_POSITION_ASSETS, PAUSE_DEPOSIT_COLLATERAL};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DepositPositionToken<'info> {
+    #[account(mut)]
+    pub owner:Signer<'info>,
+    #[account(
+        seeds=[b"config"],
+        bump=config.bump
+    )]
+    pub config:Account<'info,Config>,
+    #[account(
+        mut,
+        seeds=[b"position", owner.key().as_ref()],
+        bump=position.bump
+    )]
+    pub position:Account<'info,Position>,
+    #[account(
+        seeds=[b"collateral_type", collateral_mint.key().as_ref()],
+        bump=collateral_type.bump,
+        has_one=vault
+    )]
+    pub collateral_type:Account<'info,CollateralType>,
+    #[account(
+        mut,
+        token::mint=collateral_mint,
+        token::authority=owner,
+        token::token_program=collateral_token_program
+    )]
+    pub owner_collateral_account:InterfaceAccount<'info,TokenAccount>,
+    #[account(mut)]
+    pub vault:InterfaceAccount<'info,TokenAccount>,
+    pub collateral_mint:InterfaceAccount<'info,Mint>,
+    pub collateral_token_program:Interface<'info,TokenInterface>,
+}
+
+//  1. Move the tokens into the collateral type's vault
+//  2. Add to the position's balance of this collateral type, opening a new slot if needed
+
+pub fn process_deposit_position_token(ctx:Context<DepositPositionToken>, amount:u64) -> Result<()> {
+    require!(!ctx.accounts.config.is_paused(PAUSE_DEPOSIT_COLLATERAL), ErrorCode::ProtocolPaused);
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    // 1. move the tokens into the vault
+    let context = CpiContext::new(
+        ctx.accounts.collateral_token_program.to_account_info(),
+        TransferChecked {
+            from:ctx.accounts.owner_collateral_account.to_account_info(),
+            mint:ctx.accounts.collateral_mint.to_account_info(),
+            to:ctx.accounts.vault.to_account_info(),
+            authority:ctx.accounts.owner.to_account_info(),
+        });
+    transfer_checked(context, amount, ctx.accounts.collateral_mint.decimals)?;
+
+    // 2. update the position
+    let collateral_type = ctx.accounts.collateral_type.key();
+    let position = &mut ctx.accounts.position;
+    match position.balance_index(&collateral_type) {
+        Some(index) => {
+            let balance = &mut position.balances[index];
+            balance.amount = balance.amount.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+        }
+        None => {
+            require!(position.balances.len() < MAX_POSITION_ASSETS as usize, ErrorCode::PositionFull);
+            position.balances.push(PositionBalance { collateral_type, amount, debt: 0 });
+        }
+    }
+
+    emit_cpi!(CollateralDeposited {
+        owner: ctx.accounts.owner.key(),
+        position: position.key(),
+        collateral_type: Some(collateral_type),
+        amount,
+    });
+    Ok(())
+}
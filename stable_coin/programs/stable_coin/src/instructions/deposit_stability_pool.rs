@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked}};
+
+use crate::{error::ErrorCode, events::StabilityPoolDeposited, state::{Config, PoolDeposit, StabilityPool}, PAUSE_STABILITY_POOL};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DepositStabilityPool<'info> {
+    #[account(mut)]
+    pub depositor:Signer<'info>,
+    #[account(
+        mut,
+        associated_token::mint=mint,
+        associated_token::authority=depositor,
+        associated_token::token_program=token_program
+    )]
+    pub depositor_token_account:InterfaceAccount<'info,TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer=depositor,
+        seeds=[b"pool_deposit", depositor.key().as_ref()],
+        space= 8 + PoolDeposit::INIT_SPACE,
+        bump
+    )]
+    pub pool_deposit:Account<'info,PoolDeposit>,
+    #[account(
+        mut,
+        seeds=[b"stability_pool"],
+        bump=stability_pool.bump,
+        has_one=token_account
+    )]
+    pub stability_pool:Account<'info,StabilityPool>,
+    #[account(mut)]
+    pub token_account:InterfaceAccount<'info,TokenAccount>,
+    #[account(
+        seeds=[b"config"],
+        bump=config.bump
+    )]
+    pub config:Account<'info,Config>,
+    #[account(
+        seeds=[b"jacked_nerd"],
+        bump=config.bump_mint_acc
+    )]
+    pub mint:InterfaceAccount<'info,Mint>,
+    pub token_program:Interface<'info,TokenInterface>,
+    pub system_program:Program<'info,System>,
+    pub associated_token_program:Program<'info,AssociatedToken>
+}
+
+//  1. Pay out the SOL the existing deposit earned and compound it
+//  2. Move the new stablecoins into the pool
+//  3. Snapshot the pool for the grown deposit
+
+pub fn process_deposit_stability_pool(ctx:Context<DepositStabilityPool>, amount:u64) -> Result<()> {
+    require!(!ctx.accounts.config.is_paused(PAUSE_STABILITY_POOL), ErrorCode::ProtocolPaused);
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    let pool = &mut ctx.accounts.stability_pool;
+    let pool_deposit = &mut ctx.accounts.pool_deposit;
+    if pool_deposit.depositor == Pubkey::default() {
+        pool_deposit.depositor = ctx.accounts.depositor.key();
+        pool_deposit.bump = ctx.bumps.pool_deposit;
+    }
+
+    // 1. the pool is owned by this program, so its SOL gains are moved directly
+    let compounded = pool.compounded_deposit(pool_deposit)?;
+    let collateral_gain = pool.collateral_gain(pool_deposit)?;
+    if collateral_gain > 0 {
+        **pool.to_account_info().try_borrow_mut_lamports()? -= collateral_gain;
+        **ctx.accounts.depositor.to_account_info().try_borrow_mut_lamports()? += collateral_gain;
+    }
+
+    // 2. deposit the stablecoins
+    let context = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from:ctx.accounts.depositor_token_account.to_account_info(),
+            mint:ctx.accounts.mint.to_account_info(),
+            to:ctx.accounts.token_account.to_account_info(),
+            authority:ctx.accounts.depositor.to_account_info(),
+        });
+    transfer_checked(context, amount, ctx.accounts.mint.decimals)?;
+
+    // 3. snapshot the pool for the new balance
+    let deposit = compounded.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+    pool.total_deposits = pool.total_deposits.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+    pool_deposit.snapshot(deposit, pool);
+
+    emit_cpi!(StabilityPoolDeposited {
+        depositor: ctx.accounts.depositor.key(),
+        amount,
+        deposit,
+        collateral_gain,
+    });
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{error::ErrorCode, events::{CollateralDeposited, PositionOpened}, state::{CollateralType, Config, ProtocolState, TokenCollateral}, PAUSE_DEPOSIT_COLLATERAL};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DepositTokenCollateral<'info> {
+    #[account(mut)]
+    pub depositer:Signer<'info>,
+    #[account(
+        seeds=[b"config"],
+        bump=config.bump
+    )]
+    pub config:Account<'info,Config>,
+    #[account(
+        mut,
+        seeds=[b"protocol_state"],
+        bump=protocol_state.bump
+    )]
+    pub protocol_state:Account<'info,ProtocolState>,
+    #[account(
+        seeds=[b"collateral_type", collateral_mint.key().as_ref()],
+        bump=collateral_type.bump,
+        has_one=vault
+    )]
+    pub collateral_type:Account<'info,CollateralType>,
+    #[account(
+        init_if_needed,
+        payer=depositer,
+        seeds=[b"collateral", depositer.key().as_ref(), collateral_type.key().as_ref()],
+        space= 8 + TokenCollateral::INIT_SPACE,
+        bump
+    )]
+    pub token_collateral:Account<'info,TokenCollateral>,
+    #[account(
+        mut,
+        token::mint=collateral_mint,
+        token::authority=depositer,
+        token::token_program=collateral_token_program
+    )]
+    pub depositer_collateral_account:InterfaceAccount<'info,TokenAccount>,
+    #[account(mut)]
+    pub vault:InterfaceAccount<'info,TokenAccount>,
+    pub collateral_mint:InterfaceAccount<'info,Mint>,
+    pub collateral_token_program:Interface<'info,TokenInterface>,
+    pub system_program:Program<'info,System>,
+}
+
+    // 1. Checking if this is initial deposit
+    // 2. Move the tokens into the collateral type's vault
+    // 3. Updating user state
+
+pub fn process_deposit_token_collateral(ctx:Context<DepositTokenCollateral>, amount:u64) -> Result<()> {
+    require!(!ctx.accounts.config.is_paused(PAUSE_DEPOSIT_COLLATERAL), ErrorCode::ProtocolPaused);
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    // 1. checking if this is initial deposit
+    let position = &mut ctx.accounts.token_collateral;
+    let opened = !position.is_initialized;
+    if opened {
+        position.depositer = ctx.accounts.depositer.key();
+        position.collateral_type = ctx.accounts.collateral_type.key();
+        position.bump = ctx.bumps.token_collateral;
+        position.is_initialized = true;
+        ctx.accounts.protocol_state.open_position()?;
+    }
+
+    // 2. move the tokens into the vault
+    let context = CpiContext::new(
+        ctx.accounts.collateral_token_program.to_account_info(),
+        TransferChecked {
+            from:ctx.accounts.depositer_collateral_account.to_account_info(),
+            mint:ctx.accounts.collateral_mint.to_account_info(),
+            to:ctx.accounts.vault.to_account_info(),
+            authority:ctx.accounts.depositer.to_account_info(),
+        });
+    transfer_checked(context, amount, ctx.accounts.collateral_mint.decimals)?;
+
+    // 3. updating user state
+    position.amount = position.amount.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+
+    let owner = ctx.accounts.depositer.key();
+    let position = position.key();
+    let collateral_type = Some(ctx.accounts.collateral_type.key());
+    if opened {
+        emit_cpi!(PositionOpened { owner, position, collateral_type });
+    }
+    emit_cpi!(CollateralDeposited { owner, position, collateral_type, amount });
+    Ok(())
+}
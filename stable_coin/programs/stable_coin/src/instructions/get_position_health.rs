@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+use crate::{calculate_health_factor, lamports_to_usd, liquidation_price, math::{Rounding, WAD}, max_debt_for_collateral, min_collateral_for_debt, oracle_price, state::{Collateral, Config}, usd_to_lamports, PriceBound};
+
+#[derive(Accounts)]
+pub struct GetPositionHealth<'info> {
+    #[account(
+        seeds=[b"config"],
+        bump=config.bump
+    )]
+    pub config:Account<'info,Config>,
+    #[account(
+        seeds=[b"collateral", collateral_account.depositer.as_ref()],
+        bump=collateral_account.bump
+    )]
+    pub collateral_account:Account<'info,Collateral>,
+    pub price_update:Account<'info,PriceUpdateV2>,
+}
+
+// Returned by `get_position_health`. Debt includes the stability fee accrued up to
+// the current slot, prices are WAD and health factors are WAD.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PositionHealth {
+    pub debt:u64,
+    // health factor at the price liquidations are judged at
+    pub health_factor:u128,
+    pub liquidatable:bool,
+    // stablecoins `mint_stable` would still accept, debt ceilings included, 0 during an auction
+    pub max_mintable:u64,
+    // lamports `withdraw_collateral` would still accept, 0 during an auction
+    pub max_withdrawable_lamports:u64,
+    // SOL price, at the high end of the confidence interval, at which the position becomes liquidatable
+    pub liquidation_price:u128,
+}
+
+// Read-only view of a position, returned through the transaction return data so it
+// can be read with simulateTransaction or from another program through CPI.
+// Runs the same checks and rounding as the instructions it describes.
+pub fn process_get_position_health(ctx:Context<GetPositionHealth>) -> Result<PositionHealth> {
+    let collateral = &ctx.accounts.collateral_account;
+
+    // accrue on a copy so the view never writes to the config
+    let mut config = (*ctx.accounts.config).clone();
+    config.accrue_interest()?;
+    let debt = config.debt_of(collateral.coins)?;
+
+    // liquidations are judged at the high end of the confidence interval
+    let liquidation_side_price = oracle_price(&ctx.accounts.price_update, &config, PriceBound::Upper)?;
+    let liquidation_side_value = lamports_to_usd(collateral.lamports, liquidation_side_price, Rounding::Down)?;
+    let health_factor = calculate_health_factor(debt, liquidation_side_value, config.liq_thx)?;
+
+    // minting and withdrawing value the collateral at the low end
+    let price_in_usd = oracle_price(&ctx.accounts.price_update, &config, PriceBound::Lower)?;
+    let collateral_in_usd = lamports_to_usd(collateral.lamports, price_in_usd, Rounding::Down)?;
+
+    // a position in auction can neither mint nor withdraw until the auction settles
+    let (max_mintable, max_withdrawable_lamports) = if collateral.in_auction {
+        (0, 0)
+    } else {
+        let max_debt = max_debt_for_collateral(collateral_in_usd, config.liq_thx, config.min_health_factor)?;
+        let max_mintable = config.max_mintable(collateral.coins, max_debt)?;
+
+        let required_in_usd = min_collateral_for_debt(debt, config.liq_thx, config.min_health_factor)?;
+        let required_lamports = usd_to_lamports(required_in_usd, price_in_usd, Rounding::Up)?;
+        (max_mintable, collateral.lamports.saturating_sub(required_lamports))
+    };
+
+    Ok(PositionHealth {
+        debt,
+        health_factor,
+        liquidatable: health_factor < WAD,
+        max_mintable,
+        max_withdrawable_lamports,
+        liquidation_price: liquidation_price(debt, collateral.lamports, config.liq_thx)?,
+    })
+}
@@ -1,8 +1,10 @@
 use anchor_lang::{prelude::*, solana_program::program::invoke_signed};
 use anchor_spl::token_interface::{Mint, TokenInterface};
 use mpl_token_metadata::{instructions::{CreateMetadataAccountV3Cpi, CreateMetadataAccountV3InstructionArgs, CreateV1Cpi, CreateV1InstructionArgs}, types::{ DataV2, TokenStandard}};
-use crate::{state::Config};
+use pyth_solana_receiver_sdk::price_update::get_feed_id_from_hex;
+use crate::{events::ConfigUpdated, math::WAD, state::{Config, PriceMode, ProtocolState}, validate_risk_params, DEFAULT_AUCTION_BUFFER_BPS, DEFAULT_AUCTION_DURATION, DEFAULT_AUCTION_FLOOR_BPS, DEFAULT_MAX_CONF_BPS, DEFAULT_MAX_PRICE_AGE, SOL_USDC_FEED_ID};
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct InitConfig<'info> {
  #[account(mut)]
@@ -15,6 +17,14 @@ pub struct InitConfig<'info> {
     bump
  )]
  pub config:Account<'info, Config>,
+ #[account(
+    init,
+    payer=admin,
+    seeds=[b"protocol_state"],
+    space= 8 + ProtocolState::INIT_SPACE,
+    bump
+ )]
+ pub protocol_state:Account<'info, ProtocolState>,
 
  #[account(
     init,
@@ -45,19 +55,59 @@ pub fn process_config(
     close_factor:u64
 ) -> Result<()> {
 
+    validate_risk_params(liq_thx, liq_bonus, min_health_factor, close_factor)?;
+
     let config_account = &mut ctx.accounts.config;
 
     config_account.set_inner(Config { 
-        authority: ctx.accounts.mint.mint_authority.unwrap(),
+        authority: ctx.accounts.admin.key(),
+        pending_authority: None,
+        guardian: None,
+        paused_flags: 0,
         mint_address: ctx.accounts.mint.key(),
         liq_thx: liq_thx,
         liq_bonus: liq_bonus,
         min_health_factor: min_health_factor,
         bump: ctx.bumps.config,
         bump_mint_acc: ctx.bumps.mint,
-        close_factor:close_factor
+        close_factor:close_factor,
+        feed_id: get_feed_id_from_hex(SOL_USDC_FEED_ID)?,
+        max_price_age: DEFAULT_MAX_PRICE_AGE,
+        max_conf_bps: DEFAULT_MAX_CONF_BPS,
+        price_mode: PriceMode::SpotAndEma,
+        stability_fee_rate: 0,
+        debt_index: WAD,
+        last_accrual: Clock::get()?.unix_timestamp,
+        mint_fee_bps: 0,
+        redemption_fee_bps: 0,
+        liquidation_fee_bps: 0,
+        // uncapped until governance sets the ceilings
+        global_debt_ceiling: u64::MAX,
+        max_debt_per_position: u64::MAX,
+        total_debt: 0,
+        auction_buffer_bps: DEFAULT_AUCTION_BUFFER_BPS,
+        auction_duration: DEFAULT_AUCTION_DURATION,
+        auction_floor_bps: DEFAULT_AUCTION_FLOOR_BPS,
+        redemption_base_rate: 0,
+        last_redemption: Clock::get()?.unix_timestamp,
+        psm_fee_in_bps: 0,
+        psm_fee_out_bps: 0,
+        // the PSM stays closed until governance sets a ceiling
+        psm_debt_ceiling: 0,
+        psm_debt: 0,
+        surplus: 0,
      });
     
+    ctx.accounts.protocol_state.set_inner(ProtocolState {
+        total_collateral_lamports: 0,
+        total_debt: 0,
+        position_count: 0,
+        total_liquidated: 0,
+        cumulative_fees: 0,
+        bad_debt: 0,
+        bump: ctx.bumps.protocol_state,
+    });
+
     // Build the CPI to Metaplex Token Metadata
   let metadata_data = DataV2 {
         name: "Jacked Nerd".to_string(),
@@ -127,5 +177,18 @@ pub fn process_config(
     cpi_context1.invoke_signed(signer_seeds)?;
 
     msg!("Success attaching metadata");
+
+    // the initial risk parameters are reported as an update from zero
+    emit_cpi!(ConfigUpdated {
+        authority: ctx.accounts.admin.key(),
+        old_liq_thx: 0,
+        new_liq_thx: liq_thx,
+        old_liq_bonus: 0,
+        new_liq_bonus: liq_bonus,
+        old_min_health_factor: 0,
+        new_min_health_factor: min_health_factor,
+        old_close_factor: 0,
+        new_close_factor: close_factor,
+    });
     Ok(())
 }
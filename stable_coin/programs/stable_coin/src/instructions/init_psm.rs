@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{error::ErrorCode, events::PsmInitialized, state::{Config, Psm}};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitPsm<'info> {
+    #[account(mut)]
+    pub authority:Signer<'info>,
+    #[account(
+        seeds=[b"config"],
+        bump=config.bump,
+        has_one=authority @ ErrorCode::Unauthorized
+    )]
+    pub config:Account<'info,Config>,
+    #[account(
+        init,
+        payer=authority,
+        seeds=[b"psm"],
+        space= 8 + Psm::INIT_SPACE,
+        bump
+    )]
+    pub psm:Account<'info,Psm>,
+    #[account(
+        init,
+        payer=authority,
+        seeds=[b"psm_vault"],
+        token::mint=reserve_mint,
+        token::authority=psm,
+        token::token_program=reserve_token_program,
+        bump
+    )]
+    pub psm_vault:InterfaceAccount<'info,TokenAccount>,
+    #[account(mint::token_program=reserve_token_program)]
+    pub reserve_mint:InterfaceAccount<'info,Mint>,
+    // SPL Token or Token-2022, whichever owns the reserve mint
+    pub reserve_token_program:Interface<'info,TokenInterface>,
+    pub system_program:Program<'info,System>,
+}
+
+// Creates the PSM for `reserve_mint` and the vault holding its reserve. Swaps stay closed
+// until `update_psm` raises the debt ceiling above zero.
+pub fn process_init_psm(ctx:Context<InitPsm>) -> Result<()> {
+    ctx.accounts.psm.set_inner(Psm {
+        reserve_mint: ctx.accounts.reserve_mint.key(),
+        vault: ctx.accounts.psm_vault.key(),
+        decimals: ctx.accounts.reserve_mint.decimals,
+        bump: ctx.bumps.psm,
+        bump_vault: ctx.bumps.psm_vault,
+    });
+
+    emit_cpi!(PsmInitialized {
+        authority: ctx.accounts.authority.key(),
+        psm: ctx.accounts.psm.key(),
+        reserve_mint: ctx.accounts.reserve_mint.key(),
+        vault: ctx.accounts.psm_vault.key(),
+    });
+    Ok(())
+}
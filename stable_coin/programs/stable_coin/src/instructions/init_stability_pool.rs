@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{error::ErrorCode, events::StabilityPoolInitialized, state::{Config, StabilityPool}};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitStabilityPool<'info> {
+    #[account(mut)]
+    pub authority:Signer<'info>,
+    #[account(
+        seeds=[b"config"],
+        bump=config.bump,
+        has_one=authority @ ErrorCode::Unauthorized
+    )]
+    pub config:Account<'info,Config>,
+    #[account(
+        init,
+        payer=authority,
+        seeds=[b"stability_pool"],
+        space= StabilityPool::space(0),
+        bump
+    )]
+    pub stability_pool:Account<'info,StabilityPool>,
+    #[account(
+        init,
+        payer=authority,
+        seeds=[b"stability_pool_token_account"],
+        token::mint=mint,
+        token::authority=stability_pool,
+        token::token_program=token_program,
+        bump
+    )]
+    pub stability_pool_token_account:InterfaceAccount<'info,TokenAccount>,
+    #[account(
+        seeds=[b"jacked_nerd"],
+        bump=config.bump_mint_acc
+    )]
+    pub mint:InterfaceAccount<'info,Mint>,
+    pub token_program:Interface<'info,TokenInterface>,
+    pub system_program:Program<'info,System>,
+}
+
+// Creates the stability pool and the token account holding its deposits.
+pub fn process_init_stability_pool(ctx:Context<InitStabilityPool>) -> Result<()> {
+    ctx.accounts.stability_pool.set_inner(StabilityPool::new(
+        ctx.accounts.stability_pool_token_account.key(),
+        ctx.bumps.stability_pool,
+        ctx.bumps.stability_pool_token_account,
+    ));
+
+    emit_cpi!(StabilityPoolInitialized {
+        authority: ctx.accounts.authority.key(),
+        pool: ctx.accounts.stability_pool.key(),
+        token_account: ctx.accounts.stability_pool_token_account.key(),
+    });
+    Ok(())
+}
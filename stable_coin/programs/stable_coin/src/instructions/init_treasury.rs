@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{error::ErrorCode, events::TreasuryInitialized, state::{Config, Treasury}};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitTreasury<'info> {
+    #[account(mut)]
+    pub authority:Signer<'info>,
+    #[account(
+        seeds=[b"config"],
+        bump=config.bump,
+        has_one=authority @ ErrorCode::Unauthorized
+    )]
+    pub config:Account<'info,Config>,
+    #[account(
+        init,
+        payer=authority,
+        seeds=[b"treasury"],
+        space= 8 + Treasury::INIT_SPACE,
+        bump
+    )]
+    pub treasury:Account<'info,Treasury>,
+    #[account(
+        init,
+        payer=authority,
+        seeds=[b"treasury_token_account"],
+        token::mint=mint,
+        token::authority=treasury,
+        token::token_program=token_program,
+        bump
+    )]
+    pub treasury_token_account:InterfaceAccount<'info,TokenAccount>,
+    #[account(
+        seeds=[b"jacked_nerd"],
+        bump=config.bump_mint_acc
+    )]
+    pub mint:InterfaceAccount<'info,Mint>,
+    pub token_program:Interface<'info,TokenInterface>,
+    pub system_program:Program<'info,System>,
+}
+
+// Creates the treasury and the token account its stablecoin fees are minted into.
+// Must run before any fee charging instruction can be used.
+pub fn process_init_treasury(ctx:Context<InitTreasury>) -> Result<()> {
+    ctx.accounts.treasury.set_inner(Treasury {
+        token_account: ctx.accounts.treasury_token_account.key(),
+        bump: ctx.bumps.treasury,
+        bump_token_account: ctx.bumps.treasury_token_account,
+    });
+
+    emit_cpi!(TreasuryInitialized {
+        authority: ctx.accounts.authority.key(),
+        treasury: ctx.accounts.treasury.key(),
+        token_account: ctx.accounts.treasury_token_account.key(),
+    });
+    Ok(())
+}
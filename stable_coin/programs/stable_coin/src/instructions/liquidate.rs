@@ -1,10 +1,11 @@
-use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
-use anchor_spl::{associated_token::AssociatedToken, token_2022::{burn, Burn}, token_interface::{Mint, TokenAccount, TokenInterface}};
-use pyth_solana_receiver_sdk::price_update::{get_feed_id_from_hex, PriceUpdateV2};
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface}};
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
 
-use crate::{burn_tokens, calculate_health_factor, error::ErrorCode, integer_usd_from_pyth, lamports_to_usd, state::{Collateral, Config}, usd_to_lamports, COLLATERALSEED, MINTSEED, SOL_USDC_FEED_ID};
+use crate::{burn_tokens, error::ErrorCode, events::Liquidated, oracle_price, quote_sol_liquidation, settle_sol_liquidation, state::{Collateral, Config, ProtocolState, Treasury}, transfer_from_vault, PriceBound, PAUSE_LIQUIDATE};
 
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Liquidate<'info>{
     #[account(mut)]
@@ -31,6 +32,12 @@ pub struct Liquidate<'info>{
         bump
     )]
     pub config:Account<'info,Config>,
+    #[account(
+        mut,
+        seeds=[b"protocol_state"],
+        bump=protocol_state.bump
+    )]
+    pub protocol_state:Account<'info,ProtocolState>,
     #[account(
         mut,
         seeds=[b"jacked_nerd"],
@@ -40,6 +47,12 @@ pub struct Liquidate<'info>{
         bump
     )]
     pub mint:InterfaceAccount<'info,Mint>,
+    #[account(
+        mut,
+        seeds=[b"treasury"],
+        bump=treasury.bump
+    )]
+    pub treasury:Account<'info,Treasury>,
     pub system_program:Program<'info,System>,
     pub price_update:Account<'info, PriceUpdateV2>,
     pub token_program_2022: Interface<'info, TokenInterface>,
@@ -57,73 +70,61 @@ pub struct Liquidate<'info>{
 // Burn the liquidator’s repaid stablecoins.
 
 pub fn process_liquidate(ctx:Context<Liquidate>, coin_amount:u64)-> Result<()>{
+    require!(!ctx.accounts.config.is_paused(PAUSE_LIQUIDATE), ErrorCode::ProtocolPaused);
+    require!(!ctx.accounts.collateral_account.in_auction, ErrorCode::PositionInAuction);
 
     let collateral_account = &mut ctx.accounts.collateral_account;
     // borrower's sol account
-    let sol_account = &mut ctx.accounts.sol_account;
-    let collateral_token_acc = &mut  ctx.accounts.liquidator_token_account;
-    let price = &mut ctx.accounts.price_update;
+    let sol_account = &ctx.accounts.sol_account;
     let config = &mut ctx.accounts.config;
+    config.accrue_interest()?;
 
-    let clock = &Clock::get()?;
-    let feed_id = get_feed_id_from_hex(SOL_USDC_FEED_ID)?;
-    let price = price.get_price_no_older_than(clock, 3600, &feed_id)?;
-
-    let price_in_usd = integer_usd_from_pyth(price.price, price.exponent);
-
-    let collateral_amount_in_usd = lamports_to_usd(collateral_account.lamports, (price_in_usd as u64))?;
-
-    // Ensuring that account is unhealthy
-    let health_factor = calculate_health_factor(
-        collateral_account.lamports,
-        collateral_amount_in_usd,
-        config.liq_thx);
-    // stop liquidation is account is healthy
-    if health_factor >= 1 {
-        return Err(ErrorCode::HealthFactorError.into())
-    }
-    let max_liquidation_amount = config.close_factor.checked_mul(collateral_account.lamports).unwrap().checked_div(10000).unwrap();
-
-    let usd_price = integer_usd_from_pyth(price.price, price.exponent);
-    let coin_amount_in_lamports = usd_to_lamports(coin_amount, (usd_price as u64))?;
-    let bonus_amount = coin_amount_in_lamports.checked_mul(config.liq_bonus).unwrap().checked_div(100000).unwrap();
-
-    let total_coin_amount_in_lamports = coin_amount_in_lamports.checked_add(bonus_amount).unwrap();
+    // liquidations are judged at the high end of the confidence interval
+    let price_in_usd = oracle_price(&ctx.accounts.price_update, config, PriceBound::Upper)?;
+    let liquidation = quote_sol_liquidation(collateral_account, config, price_in_usd, coin_amount)?;
 
-    if total_coin_amount_in_lamports > max_liquidation_amount {
-        return Err(ErrorCode::MaxLiquidationAmount.into())
-    }
-    let liquidator = &mut ctx.accounts.liquidator;
     // transfer the coins of the user to the protocol
     burn_tokens(
         &ctx.accounts.mint,
         &ctx.accounts.token_program_2022,
         &ctx.accounts.liquidator_token_account,
         coin_amount,
-        liquidator
-    );
-
-    // transfer the collateral of the user to the liquidator
-    let signer_seeds: &[&[&[u8]]] = &[&[
-    COLLATERALSEED,
-    collateral_account.depositer.as_ref(),
-    &[collateral_account.bump_sol_account]
-]];
-
-    let ctx = CpiContext::new_with_signer(
-        ctx.accounts.system_program.to_account_info(),
-        Transfer{
-        from:sol_account.to_account_info(),
-        to:ctx.accounts.liquidator.to_account_info()
-        },
-        signer_seeds);
-
-    transfer(ctx,total_coin_amount_in_lamports)?;
+        &ctx.accounts.liquidator
+    )?;
+
+    // transfer the collateral of the user to the liquidator, and the fee to the treasury
+    transfer_from_vault(
+        &ctx.accounts.system_program,
+        sol_account,
+        &ctx.accounts.liquidator.to_account_info(),
+        &collateral_account.depositer,
+        collateral_account.bump_sol_account,
+        liquidation.amounts.seized
+    )?;
+    if liquidation.amounts.fee > 0 {
+        transfer_from_vault(
+            &ctx.accounts.system_program,
+            sol_account,
+            &ctx.accounts.treasury.to_account_info(),
+            &collateral_account.depositer,
+            collateral_account.bump_sol_account,
+            liquidation.amounts.fee
+        )?;
+    }
 
     // Updating States of the protocol
-    collateral_account.coins = collateral_account.coins.checked_sub(coin_amount).unwrap();
-    collateral_account.lamports = collateral_account.lamports.checked_sub(total_coin_amount_in_lamports).unwrap();
-    
+    let new_health_factor = settle_sol_liquidation(collateral_account, config, &mut ctx.accounts.protocol_state, &liquidation, coin_amount, price_in_usd)?;
+
+    emit_cpi!(Liquidated {
+        liquidator: ctx.accounts.liquidator.key(),
+        position: ctx.accounts.collateral_account.key(),
+        collateral_type: None,
+        repaid: coin_amount,
+        seized: liquidation.amounts.seized,
+        fee: liquidation.amounts.fee,
+        price: price_in_usd,
+        health_factor: new_health_factor,
+    });
     Ok(())
 
-}
\ No newline at end of file
+}
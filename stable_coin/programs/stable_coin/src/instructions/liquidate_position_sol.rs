@@ -0,0 +1,155 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+use crate::{assign_position_debt, burn_tokens, error::ErrorCode, events::Liquidated, health_factor_from_weighted, lamports_to_usd, liquidation_amounts, risk_weighted_value, math::{Rounding, WAD}, repaid_position_debt_shares, state::{Config, Position, ProtocolState, Treasury}, transfer_from_position_vault, usd_to_lamports, value_position, PriceBound, PAUSE_LIQUIDATE};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct LiquidatePositionSol<'info>{
+    #[account(mut)]
+    pub liquidator:Signer<'info>,
+    #[account(
+        mut,
+        seeds=[b"config"],
+        bump=config.bump
+    )]
+    pub config:Account<'info,Config>,
+    #[account(
+        mut,
+        seeds=[b"protocol_state"],
+        bump=protocol_state.bump
+    )]
+    pub protocol_state:Account<'info,ProtocolState>,
+    #[account(
+        mut,
+        seeds=[b"position", position.owner.as_ref()],
+        bump=position.bump
+    )]
+    pub position:Account<'info,Position>,
+    /// SAFETY: This account is only used as a recipient for SOL transfers.
+    /// The seeds ensure that the PDA is derived deterministically and cannot be arbitrarily passed in by the client.
+    #[account(
+        mut,
+        seeds=[b"position_sol_account", position.owner.as_ref()],
+        bump=position.bump_sol_account
+    )]
+    pub position_sol_account:AccountInfo<'info>,
+    #[account(
+        mut,
+        associated_token::mint=mint,
+        associated_token::authority=liquidator,
+        associated_token::token_program=token_program_2022
+    )]
+    pub liquidator_token_account:InterfaceAccount<'info,TokenAccount>,
+    #[account(
+        mut,
+        seeds=[b"jacked_nerd"],
+        mint::authority=mint,
+        mint::freeze_authority=mint,
+        mint::token_program=token_program_2022,
+        bump
+    )]
+    pub mint:InterfaceAccount<'info,Mint>,
+    #[account(
+        mut,
+        seeds=[b"treasury"],
+        bump=treasury.bump
+    )]
+    pub treasury:Account<'info,Treasury>,
+    // SOL/USD price, the other assets are priced through `remaining_accounts`
+    pub price_update:Account<'info, PriceUpdateV2>,
+    pub system_program:Program<'info,System>,
+    pub token_program_2022: Interface<'info, TokenInterface>,
+}
+
+// Same flow as `liquidate`, but the position is judged on all of its assets and
+// the repaid debt is taken out of its SOL balance.
+// 1 Make sure the position is unhealthy
+// 2 Convert the repaid stablecoins plus bonus into lamports
+// 3 Burn the liquidator's repaid stablecoins
+// 4 Transfer seized SOL → liquidator, fee → treasury
+// 5 Reduce the position's debt and SOL balance, releasing the repaid part of each SPL balance's
+//   share of the debt from its collateral type
+
+pub fn process_liquidate_position_sol(ctx:Context<LiquidatePositionSol>, coin_amount:u64) -> Result<()> {
+    require!(!ctx.accounts.config.is_paused(PAUSE_LIQUIDATE), ErrorCode::ProtocolPaused);
+    require!(coin_amount > 0, ErrorCode::InvalidAmount);
+
+    let config = &mut ctx.accounts.config;
+    config.accrue_interest()?;
+    let position = &mut ctx.accounts.position;
+
+    // 1. liquidations are judged at the high end of every price
+    let value = value_position(position, config, &ctx.accounts.price_update, ctx.remaining_accounts, PriceBound::Upper)?;
+    let debt = config.debt_of(position.coins)?;
+    let health_factor = health_factor_from_weighted(debt, value.weighted_collateral)?;
+    if health_factor >= WAD {
+        return Err(ErrorCode::HealthFactorError.into())
+    }
+    let repaid_coins = config.normalized_repayment(position.coins, coin_amount)?;
+
+    // 2. SOL owed to the liquidator and the treasury, capped by the close factor of the SOL balance, or by
+    //    the whole balance once the position is worth less than its debt
+    let underwater = value.collateral_in_usd < debt;
+    let coin_amount_in_lamports = usd_to_lamports(coin_amount, value.sol_price, Rounding::Down)?;
+    let amounts = liquidation_amounts(coin_amount_in_lamports, position.lamports, config.liq_bonus, config.liquidation_fee_bps, config.close_factor, underwater)?;
+    let total_seized = amounts.total()?;
+
+    // 3. burn the repaid stablecoins
+    burn_tokens(
+        &ctx.accounts.mint,
+        &ctx.accounts.token_program_2022,
+        &ctx.accounts.liquidator_token_account,
+        coin_amount,
+        &ctx.accounts.liquidator
+    )?;
+
+    // 4. transfer the seized SOL to the liquidator and the fee to the treasury
+    transfer_from_position_vault(
+        &ctx.accounts.system_program,
+        &ctx.accounts.position_sol_account,
+        &ctx.accounts.liquidator.to_account_info(),
+        &position.owner,
+        position.bump_sol_account,
+        amounts.seized
+    )?;
+    if amounts.fee > 0 {
+        transfer_from_position_vault(
+            &ctx.accounts.system_program,
+            &ctx.accounts.position_sol_account,
+            &ctx.accounts.treasury.to_account_info(),
+            &position.owner,
+            position.bump_sol_account,
+            amounts.fee
+        )?;
+    }
+
+    // 5. update state, the SPL balances keep backing what is left of the debt in the same proportions
+    let shares = repaid_position_debt_shares(position, repaid_coins)?;
+    assign_position_debt(position, config, ctx.remaining_accounts.iter().step_by(2), &shares)?;
+    position.coins = position.coins.checked_sub(repaid_coins).ok_or(ErrorCode::MathOverflow)?;
+    config.remove_debt(repaid_coins)?;
+    position.lamports = position.lamports.checked_sub(total_seized).ok_or(ErrorCode::MathOverflow)?;
+    let seized_in_usd = lamports_to_usd(total_seized, value.sol_price, Rounding::Up)?;
+    let remaining_weighted = value.weighted_collateral.saturating_sub(risk_weighted_value(seized_in_usd, config.liq_thx)?);
+    let new_health_factor = health_factor_from_weighted(config.debt_of(position.coins)?, remaining_weighted)?;
+
+    let protocol_state = &mut ctx.accounts.protocol_state;
+    protocol_state.remove_collateral(total_seized)?;
+    protocol_state.record_liquidation(coin_amount)?;
+    protocol_state.record_fee(lamports_to_usd(amounts.fee, value.sol_price, Rounding::Down)?)?;
+    protocol_state.sync_debt(config)?;
+
+    emit_cpi!(Liquidated {
+        liquidator: ctx.accounts.liquidator.key(),
+        position: position.key(),
+        collateral_type: None,
+        repaid: coin_amount,
+        seized: amounts.seized,
+        fee: amounts.fee,
+        price: value.sol_price,
+        health_factor: new_health_factor,
+    });
+    Ok(())
+}
@@ -0,0 +1,174 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+use crate::{assign_position_debt, burn_tokens, error::ErrorCode, events::Liquidated, liquidation_amounts, health_factor_from_weighted, risk_weighted_value, token_to_usd, math::{Rounding, WAD}, repaid_position_debt_shares, state::{CollateralType, Config, Position, ProtocolState}, transfer_from_collateral_vault, usd_to_token, value_position, PriceBound, PAUSE_LIQUIDATE};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct LiquidatePositionToken<'info>{
+    #[account(mut)]
+    pub liquidator:Signer<'info>,
+    #[account(
+        mut,
+        seeds=[b"config"],
+        bump=config.bump
+    )]
+    pub config:Account<'info,Config>,
+    #[account(
+        mut,
+        seeds=[b"protocol_state"],
+        bump=protocol_state.bump
+    )]
+    pub protocol_state:Account<'info,ProtocolState>,
+    #[account(
+        mut,
+        seeds=[b"position", position.owner.as_ref()],
+        bump=position.bump
+    )]
+    pub position:Account<'info,Position>,
+    #[account(
+        seeds=[b"collateral_type", collateral_mint.key().as_ref()],
+        bump=collateral_type.bump,
+        has_one=vault
+    )]
+    pub collateral_type:Account<'info,CollateralType>,
+    #[account(
+        mut,
+        associated_token::mint=mint,
+        associated_token::authority=liquidator,
+        associated_token::token_program=token_program_2022
+    )]
+    pub liquidator_token_account:InterfaceAccount<'info,TokenAccount>,
+    #[account(
+        mut,
+        token::mint=collateral_mint,
+        token::authority=liquidator,
+        token::token_program=collateral_token_program
+    )]
+    pub liquidator_collateral_account:InterfaceAccount<'info,TokenAccount>,
+    #[account(mut)]
+    pub vault:InterfaceAccount<'info,TokenAccount>,
+    #[account(
+        mut,
+        seeds=[b"treasury_collateral", collateral_mint.key().as_ref()],
+        bump
+    )]
+    pub treasury_collateral_account:InterfaceAccount<'info,TokenAccount>,
+    pub collateral_mint:InterfaceAccount<'info,Mint>,
+    #[account(
+        mut,
+        seeds=[b"jacked_nerd"],
+        mint::authority=mint,
+        mint::freeze_authority=mint,
+        mint::token_program=token_program_2022,
+        bump
+    )]
+    pub mint:InterfaceAccount<'info,Mint>,
+    // SOL/USD price, the other assets are priced through `remaining_accounts`
+    pub price_update:Account<'info, PriceUpdateV2>,
+    pub token_program_2022: Interface<'info, TokenInterface>,
+    pub collateral_token_program:Interface<'info,TokenInterface>,
+}
+
+// Same flow as `liquidate_position_sol`, seizing one of the position's SPL balances
+// with the bonus of its collateral type.
+// 1 Make sure the position is unhealthy
+// 2 Convert the repaid stablecoins plus bonus into collateral tokens
+// 3 Burn the liquidator's repaid stablecoins
+// 4 Transfer seized collateral → liquidator, fee → treasury
+// 5 Reduce the position's debt and balance, releasing the repaid part of each SPL balance's
+//   share of the debt from its collateral type
+
+pub fn process_liquidate_position_token(ctx:Context<LiquidatePositionToken>, coin_amount:u64) -> Result<()> {
+    require!(!ctx.accounts.config.is_paused(PAUSE_LIQUIDATE), ErrorCode::ProtocolPaused);
+    require!(coin_amount > 0, ErrorCode::InvalidAmount);
+
+    let config = &mut ctx.accounts.config;
+    config.accrue_interest()?;
+    let collateral_type = &ctx.accounts.collateral_type;
+    let position = &mut ctx.accounts.position;
+
+    // 1. liquidations are judged at the high end of every price
+    let value = value_position(position, config, &ctx.accounts.price_update, ctx.remaining_accounts, PriceBound::Upper)?;
+    let debt = config.debt_of(position.coins)?;
+    let health_factor = health_factor_from_weighted(debt, value.weighted_collateral)?;
+    if health_factor >= WAD {
+        return Err(ErrorCode::HealthFactorError.into())
+    }
+    let repaid_coins = config.normalized_repayment(position.coins, coin_amount)?;
+
+    // 2. collateral owed to the liquidator and the treasury, capped by the close factor of this balance, or by
+    //    the whole balance once the position is worth less than its debt
+    let underwater = value.collateral_in_usd < debt;
+    let index = position.balance_index(&collateral_type.key()).ok_or(ErrorCode::CollateralNotInPosition)?;
+    let balance = position.balances[index].amount;
+    let coin_amount_in_tokens = usd_to_token(coin_amount, collateral_type.decimals, value.token_prices[index], Rounding::Down)?;
+    let amounts = liquidation_amounts(coin_amount_in_tokens, balance, collateral_type.liq_bonus, config.liquidation_fee_bps, config.close_factor, underwater)?;
+    let total_seized = amounts.total()?;
+
+    // 3. burn the repaid stablecoins
+    burn_tokens(
+        &ctx.accounts.mint,
+        &ctx.accounts.token_program_2022,
+        &ctx.accounts.liquidator_token_account,
+        coin_amount,
+        &ctx.accounts.liquidator
+    )?;
+
+    // 4. transfer the seized collateral to the liquidator and the fee to the treasury
+    transfer_from_collateral_vault(
+        &ctx.accounts.collateral_token_program,
+        &ctx.accounts.vault,
+        &ctx.accounts.liquidator_collateral_account,
+        &ctx.accounts.collateral_mint,
+        collateral_type,
+        amounts.seized
+    )?;
+    if amounts.fee > 0 {
+        transfer_from_collateral_vault(
+            &ctx.accounts.collateral_token_program,
+            &ctx.accounts.vault,
+            &ctx.accounts.treasury_collateral_account,
+            &ctx.accounts.collateral_mint,
+            collateral_type,
+            amounts.fee
+        )?;
+    }
+
+    // 5. update state, an emptied balance gives its whole share back and frees its slot
+    let remaining = balance.checked_sub(total_seized).ok_or(ErrorCode::MathOverflow)?;
+    let mut shares = repaid_position_debt_shares(position, repaid_coins)?;
+    if remaining == 0 {
+        shares[index] = 0;
+    }
+    assign_position_debt(position, config, ctx.remaining_accounts.iter().step_by(2), &shares)?;
+    position.coins = position.coins.checked_sub(repaid_coins).ok_or(ErrorCode::MathOverflow)?;
+    config.remove_debt(repaid_coins)?;
+    if remaining == 0 {
+        position.balances.remove(index);
+    } else {
+        position.balances[index].amount = remaining;
+    }
+    let price = value.token_prices[index];
+    let seized_in_usd = token_to_usd(total_seized, collateral_type.decimals, price, Rounding::Up)?;
+    let remaining_weighted = value.weighted_collateral.saturating_sub(risk_weighted_value(seized_in_usd, collateral_type.liq_thx)?);
+    let new_health_factor = health_factor_from_weighted(config.debt_of(position.coins)?, remaining_weighted)?;
+
+    let protocol_state = &mut ctx.accounts.protocol_state;
+    protocol_state.record_liquidation(coin_amount)?;
+    protocol_state.record_fee(token_to_usd(amounts.fee, collateral_type.decimals, price, Rounding::Down)?)?;
+    protocol_state.sync_debt(config)?;
+
+    emit_cpi!(Liquidated {
+        liquidator: ctx.accounts.liquidator.key(),
+        position: position.key(),
+        collateral_type: Some(collateral_type.key()),
+        repaid: coin_amount,
+        seized: amounts.seized,
+        fee: amounts.fee,
+        price,
+        health_factor: new_health_factor,
+    });
+    Ok(())
+}
@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+use crate::{burn_tokens, calculate_health_factor, error::ErrorCode, events::Liquidated, liquidation_amounts, feed_price, math::{Rounding, WAD}, state::{CollateralType, Config, ProtocolState, TokenCollateral}, token_to_usd, transfer_from_collateral_vault, usd_to_token, PriceBound, PAUSE_LIQUIDATE};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct LiquidateTokenCollateral<'info>{
+    #[account(mut)]
+    pub liquidator:Signer<'info>,
+    #[account(
+        mut,
+        seeds=[b"config"],
+        bump=config.bump
+    )]
+    pub config:Account<'info,Config>,
+    #[account(
+        mut,
+        seeds=[b"protocol_state"],
+        bump=protocol_state.bump
+    )]
+    pub protocol_state:Account<'info,ProtocolState>,
+    #[account(
+        mut,
+        seeds=[b"collateral_type", collateral_mint.key().as_ref()],
+        bump=collateral_type.bump,
+        has_one=vault
+    )]
+    pub collateral_type:Account<'info,CollateralType>,
+    #[account(
+        mut,
+        seeds=[b"collateral", token_collateral.depositer.as_ref(), collateral_type.key().as_ref()],
+        bump=token_collateral.bump
+    )]
+    pub token_collateral:Account<'info,TokenCollateral>,
+    #[account(
+        mut,
+        associated_token::mint=mint,
+        associated_token::authority=liquidator,
+        associated_token::token_program=token_program_2022
+    )]
+    pub liquidator_token_account:InterfaceAccount<'info,TokenAccount>,
+    #[account(
+        mut,
+        token::mint=collateral_mint,
+        token::authority=liquidator,
+        token::token_program=collateral_token_program
+    )]
+    pub liquidator_collateral_account:InterfaceAccount<'info,TokenAccount>,
+    #[account(mut)]
+    pub vault:InterfaceAccount<'info,TokenAccount>,
+    #[account(
+        mut,
+        seeds=[b"treasury_collateral", collateral_mint.key().as_ref()],
+        bump
+    )]
+    pub treasury_collateral_account:InterfaceAccount<'info,TokenAccount>,
+    pub collateral_mint:InterfaceAccount<'info,Mint>,
+    #[account(
+        mut,
+        seeds=[b"jacked_nerd"],
+        mint::authority=mint,
+        mint::freeze_authority=mint,
+        mint::token_program=token_program_2022,
+        bump
+    )]
+    pub mint:InterfaceAccount<'info,Mint>,
+    pub price_update:Account<'info, PriceUpdateV2>,
+    pub token_program_2022: Interface<'info, TokenInterface>,
+    pub collateral_token_program:Interface<'info,TokenInterface>,
+}
+
+// Same flow as `liquidate`, with the threshold and bonus of the collateral type
+// 1 Make sure the position is unhealthy
+// 2 Convert the repaid stablecoins plus bonus into collateral tokens
+// 3 Burn the liquidator's repaid stablecoins
+// 4 Transfer seized collateral → liquidator, fee → treasury
+// 5 Reduce the position's debt and collateral
+
+pub fn process_liquidate_token_collateral(ctx:Context<LiquidateTokenCollateral>, coin_amount:u64) -> Result<()> {
+    require!(!ctx.accounts.config.is_paused(PAUSE_LIQUIDATE), ErrorCode::ProtocolPaused);
+    require!(coin_amount > 0, ErrorCode::InvalidAmount);
+
+    let config = &mut ctx.accounts.config;
+    config.accrue_interest()?;
+    let collateral_type = &mut ctx.accounts.collateral_type;
+    let position = &mut ctx.accounts.token_collateral;
+
+    // 1. liquidations are judged at the high end of the confidence interval
+    let price_in_usd = feed_price(&ctx.accounts.price_update, config, &collateral_type.feed_id, PriceBound::Upper)?;
+    let collateral_in_usd = token_to_usd(position.amount, collateral_type.decimals, price_in_usd, Rounding::Down)?;
+    let debt = config.debt_of(position.coins)?;
+    let health_factor = calculate_health_factor(debt, collateral_in_usd, collateral_type.liq_thx)?;
+    if health_factor >= WAD {
+        return Err(ErrorCode::HealthFactorError.into())
+    }
+    let repaid_coins = config.normalized_repayment(position.coins, coin_amount)?;
+
+    // 2. collateral owed to the liquidator and the treasury, capped by the close factor, or by
+    //    the whole balance once the position is worth less than its debt
+    let underwater = collateral_in_usd < debt;
+    let coin_amount_in_tokens = usd_to_token(coin_amount, collateral_type.decimals, price_in_usd, Rounding::Down)?;
+    let amounts = liquidation_amounts(coin_amount_in_tokens, position.amount, collateral_type.liq_bonus, config.liquidation_fee_bps, config.close_factor, underwater)?;
+    let total_seized = amounts.total()?;
+
+    // 3. burn the repaid stablecoins
+    burn_tokens(
+        &ctx.accounts.mint,
+        &ctx.accounts.token_program_2022,
+        &ctx.accounts.liquidator_token_account,
+        coin_amount,
+        &ctx.accounts.liquidator
+    )?;
+
+    // 4. transfer the seized collateral to the liquidator and the fee to the treasury
+    transfer_from_collateral_vault(
+        &ctx.accounts.collateral_token_program,
+        &ctx.accounts.vault,
+        &ctx.accounts.liquidator_collateral_account,
+        &ctx.accounts.collateral_mint,
+        collateral_type,
+        amounts.seized
+    )?;
+    if amounts.fee > 0 {
+        transfer_from_collateral_vault(
+            &ctx.accounts.collateral_token_program,
+            &ctx.accounts.vault,
+            &ctx.accounts.treasury_collateral_account,
+            &ctx.accounts.collateral_mint,
+            collateral_type,
+            amounts.fee
+        )?;
+    }
+
+    // 5. update state
+    position.coins = position.coins.checked_sub(repaid_coins).ok_or(ErrorCode::MathOverflow)?;
+    config.remove_debt(repaid_coins)?;
+    position.amount = position.amount.checked_sub(total_seized).ok_or(ErrorCode::MathOverflow)?;
+    collateral_type.total_debt = collateral_type.total_debt.checked_sub(repaid_coins).ok_or(ErrorCode::MathOverflow)?;
+    let remaining_collateral_in_usd = token_to_usd(position.amount, collateral_type.decimals, price_in_usd, Rounding::Down)?;
+    let new_health_factor = calculate_health_factor(config.debt_of(position.coins)?, remaining_collateral_in_usd, collateral_type.liq_thx)?;
+
+    let protocol_state = &mut ctx.accounts.protocol_state;
+    protocol_state.record_liquidation(coin_amount)?;
+    protocol_state.record_fee(token_to_usd(amounts.fee, collateral_type.decimals, price_in_usd, Rounding::Down)?)?;
+    protocol_state.sync_debt(config)?;
+
+    emit_cpi!(Liquidated {
+        liquidator: ctx.accounts.liquidator.key(),
+        position: position.key(),
+        collateral_type: Some(collateral_type.key()),
+        repaid: coin_amount,
+        seized: amounts.seized,
+        fee: amounts.fee,
+        price: price_in_usd,
+        health_factor: new_health_factor,
+    });
+    Ok(())
+}
@@ -0,0 +1,133 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{token_2022::{burn, Burn}, token_interface::{Mint, TokenAccount, TokenInterface}};
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+use crate::{error::ErrorCode, events::Liquidated, oracle_price, quote_sol_liquidation, settle_sol_liquidation, state::{Collateral, Config, ProtocolState, StabilityPool, Treasury}, transfer_from_vault, PriceBound, PAUSE_LIQUIDATE, STABILITY_POOL_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct LiquidateWithPool<'info>{
+    #[account(mut)]
+    pub keeper:Signer<'info>,
+    #[account(
+        mut,
+        seeds=[b"collateral", collateral_account.depositer.as_ref()],
+        bump=collateral_account.bump,
+        has_one=sol_account
+    )]
+    pub collateral_account:Account<'info,Collateral>,
+    /// SAFETY: This account is only used as a source for SOL transfers.
+    /// `has_one` on the collateral account ties it to the liquidated position.
+    #[account(mut)]
+    pub sol_account:AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds=[b"stability_pool"],
+        bump=stability_pool.bump,
+        has_one=token_account,
+        // room for the reward sum this liquidation may open, paid for by the keeper
+        realloc=StabilityPool::space(stability_pool.sums_after_offset()),
+        realloc::payer=keeper,
+        realloc::zero=false
+    )]
+    pub stability_pool:Account<'info,StabilityPool>,
+    #[account(mut)]
+    pub token_account:InterfaceAccount<'info,TokenAccount>,
+    #[account(
+        mut,
+        seeds=[b"config"],
+        bump=config.bump
+    )]
+    pub config:Account<'info,Config>,
+    #[account(
+        mut,
+        seeds=[b"protocol_state"],
+        bump=protocol_state.bump
+    )]
+    pub protocol_state:Account<'info,ProtocolState>,
+    #[account(
+        mut,
+        seeds=[b"jacked_nerd"],
+        mint::authority=mint,
+        mint::freeze_authority=mint,
+        mint::token_program=token_program_2022,
+        bump
+    )]
+    pub mint:InterfaceAccount<'info,Mint>,
+    #[account(
+        mut,
+        seeds=[b"treasury"],
+        bump=treasury.bump
+    )]
+    pub treasury:Account<'info,Treasury>,
+    pub price_update:Account<'info,PriceUpdateV2>,
+    pub system_program:Program<'info,System>,
+    pub token_program_2022:Interface<'info,TokenInterface>,
+}
+
+//  1. Same health, close factor, bonus and fee rules as `liquidate`, through `quote_sol_liquidation`
+//  2. Burn the repaid stablecoins out of the stability pool instead of a liquidator's wallet
+//  3. Send the seized SOL and bonus to the pool and share it among the depositors
+//  4. Update the position and protocol state
+
+pub fn process_liquidate_with_pool(ctx:Context<LiquidateWithPool>, coin_amount:u64) -> Result<()> {
+    require!(!ctx.accounts.config.is_paused(PAUSE_LIQUIDATE), ErrorCode::ProtocolPaused);
+    require!(!ctx.accounts.collateral_account.in_auction, ErrorCode::PositionInAuction);
+
+    let collateral_account = &mut ctx.accounts.collateral_account;
+    let config = &mut ctx.accounts.config;
+    config.accrue_interest()?;
+
+    // 1. liquidations are judged at the high end of the confidence interval
+    let price_in_usd = oracle_price(&ctx.accounts.price_update, config, PriceBound::Upper)?;
+    let liquidation = quote_sol_liquidation(collateral_account, config, price_in_usd, coin_amount)?;
+
+    // 2. the pool PDA signs for the burn of its own deposits
+    let pool = &mut ctx.accounts.stability_pool;
+    pool.offset(coin_amount, liquidation.amounts.seized)?;
+    let signer_seeds:&[&[&[u8]]] = &[&[STABILITY_POOL_SEED, &[pool.bump]]];
+    let context = CpiContext::new_with_signer(
+        ctx.accounts.token_program_2022.to_account_info(),
+        Burn {
+            authority:pool.to_account_info(),
+            mint:ctx.accounts.mint.to_account_info(),
+            from:ctx.accounts.token_account.to_account_info(),
+        },
+        signer_seeds);
+    burn(context, coin_amount)?;
+
+    // 3. the seized collateral is held on the pool account until depositors claim it
+    transfer_from_vault(
+        &ctx.accounts.system_program,
+        &ctx.accounts.sol_account,
+        &pool.to_account_info(),
+        &collateral_account.depositer,
+        collateral_account.bump_sol_account,
+        liquidation.amounts.seized
+    )?;
+    if liquidation.amounts.fee > 0 {
+        transfer_from_vault(
+            &ctx.accounts.system_program,
+            &ctx.accounts.sol_account,
+            &ctx.accounts.treasury.to_account_info(),
+            &collateral_account.depositer,
+            collateral_account.bump_sol_account,
+            liquidation.amounts.fee
+        )?;
+    }
+
+    // 4. update the position and protocol state
+    let new_health_factor = settle_sol_liquidation(collateral_account, config, &mut ctx.accounts.protocol_state, &liquidation, coin_amount, price_in_usd)?;
+
+    emit_cpi!(Liquidated {
+        liquidator: ctx.accounts.stability_pool.key(),
+        position: ctx.accounts.collateral_account.key(),
+        collateral_type: None,
+        repaid: coin_amount,
+        seized: liquidation.amounts.seized,
+        fee: liquidation.amounts.fee,
+        price: price_in_usd,
+        health_factor: new_health_factor,
+    });
+    Ok(())
+}
@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface}};
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+use crate::{calculate_health_factor, error::ErrorCode, events::Minted, feed_price, math::Rounding, mint_with_fee, state::{CollateralType, Config, ProtocolState, TokenCollateral}, token_to_usd, PriceBound, PAUSE_MINT_STABLE};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MintAgainstTokenCollateral<'info> {
+    #[account(mut)]
+    pub minter:Signer<'info>,
+    #[account(
+        mut,
+        seeds=[b"config"],
+        bump=config.bump
+    )]
+    pub config:Account<'info,Config>,
+    #[account(
+        mut,
+        seeds=[b"protocol_state"],
+        bump=protocol_state.bump
+    )]
+    pub protocol_state:Account<'info,ProtocolState>,
+    #[account(
+        mut,
+        seeds=[b"collateral_type", collateral_type.mint.as_ref()],
+        bump=collateral_type.bump
+    )]
+    pub collateral_type:Account<'info,CollateralType>,
+    #[account(
+        mut,
+        seeds=[b"collateral", minter.key().as_ref(), collateral_type.key().as_ref()],
+        bump=token_collateral.bump
+    )]
+    pub token_collateral:Account<'info,TokenCollateral>,
+    #[account(
+        init_if_needed,
+        payer=minter,
+        associated_token::mint=mint,
+        associated_token::authority=minter,
+        associated_token::token_program=token_program_2022
+    )]
+    pub minter_token_account:InterfaceAccount<'info,TokenAccount>,
+    #[account(
+        mut,
+        seeds=[b"jacked_nerd"],
+        mint::authority=mint,
+        mint::freeze_authority=mint,
+        mint::token_program=token_program_2022,
+        bump
+    )]
+    pub mint:InterfaceAccount<'info,Mint>,
+    #[account(
+        mut,
+        seeds=[b"treasury_token_account"],
+        bump
+    )]
+    pub treasury_token_account:InterfaceAccount<'info,TokenAccount>,
+    pub price_update:Account<'info, PriceUpdateV2>,
+    pub system_program:Program<'info,System>,
+    pub token_program_2022: Interface<'info, TokenInterface>,
+    pub associated_token_program:Program<'info,AssociatedToken>
+}
+
+    // 1. Check the debt ceiling of the collateral type
+    // 2. Check the health factor of the position with the new debt
+    // 3. Mint tokens to the user, less the mint fee
+    // 4. Updating user and collateral type state
+
+pub fn process_mint_against_token_collateral(ctx:Context<MintAgainstTokenCollateral>, amount:u64) -> Result<()> {
+    require!(!ctx.accounts.config.is_paused(PAUSE_MINT_STABLE), ErrorCode::ProtocolPaused);
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    let config = &mut ctx.accounts.config;
+    config.accrue_interest()?;
+    let collateral_type = &mut ctx.accounts.collateral_type;
+    let position = &mut ctx.accounts.token_collateral;
+
+    // 1. check the debt ceiling against the accrued debt of the collateral type
+    let normalized_amount = config.normalize(amount, Rounding::Up)?;
+    let new_total_debt = collateral_type.total_debt.checked_add(normalized_amount).ok_or(ErrorCode::MathOverflow)?;
+    require!(config.debt_of(new_total_debt)? <= collateral_type.debt_ceiling, ErrorCode::CollateralDebtCeilingExceeded);
+
+    // 2. collateral is valued at the low end of the confidence interval
+    let price_in_usd = feed_price(&ctx.accounts.price_update, config, &collateral_type.feed_id, PriceBound::Lower)?;
+    let collateral_in_usd = token_to_usd(position.amount, collateral_type.decimals, price_in_usd, Rounding::Down)?;
+    let new_coins_balance = position.coins.checked_add(normalized_amount).ok_or(ErrorCode::MathOverflow)?;
+    config.add_debt(normalized_amount, new_coins_balance)?;
+
+    let new_debt = config.debt_of(new_coins_balance)?;
+    let health_factor = calculate_health_factor(new_debt, collateral_in_usd, collateral_type.liq_thx)?;
+    if health_factor < config.min_health_factor_wad()? {
+        return Err(ErrorCode::HealthFactorError.into());
+    }
+
+    // 3. mint tokens to the user, less the mint fee which goes to the treasury
+    let mint_fee = mint_with_fee(
+        &ctx.accounts.minter_token_account,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.token_program_2022,
+        &ctx.accounts.mint,
+        config.bump_mint_acc,
+        amount,
+        config.mint_fee_bps
+    )?;
+
+    // 4. updating state
+    position.coins = new_coins_balance;
+    collateral_type.total_debt = new_total_debt;
+    let protocol_state = &mut ctx.accounts.protocol_state;
+    protocol_state.record_fee(mint_fee)?;
+    protocol_state.sync_debt(config)?;
+
+    emit_cpi!(Minted {
+        owner: ctx.accounts.minter.key(),
+        position: position.key(),
+        amount,
+        fee: mint_fee,
+        debt: new_debt,
+        price: Some(price_in_usd),
+        health_factor,
+    });
+    Ok(())
+}
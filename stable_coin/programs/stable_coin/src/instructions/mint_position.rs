@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface}};
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+use crate::{assign_position_debt, error::ErrorCode, events::Minted, health_factor_from_weighted, math::Rounding, mint_with_fee, position_debt_shares, state::{Config, Position, ProtocolState}, value_position, PriceBound, PAUSE_MINT_STABLE};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MintPosition<'info> {
+    #[account(mut)]
+    pub owner:Signer<'info>,
+    #[account(
+        mut,
+        seeds=[b"config"],
+        bump=config.bump
+    )]
+    pub config:Account<'info,Config>,
+    #[account(
+        mut,
+        seeds=[b"protocol_state"],
+        bump=protocol_state.bump
+    )]
+    pub protocol_state:Account<'info,ProtocolState>,
+    #[account(
+        mut,
+        seeds=[b"position", owner.key().as_ref()],
+        bump=position.bump
+    )]
+    pub position:Account<'info,Position>,
+    #[account(
+        init_if_needed,
+        payer=owner,
+        associated_token::mint=mint,
+        associated_token::authority=owner,
+        associated_token::token_program=token_program_2022
+    )]
+    pub owner_token_account:InterfaceAccount<'info,TokenAccount>,
+    #[account(
+        mut,
+        seeds=[b"jacked_nerd"],
+        mint::authority=mint,
+        mint::freeze_authority=mint,
+        mint::token_program=token_program_2022,
+        bump
+    )]
+    pub mint:InterfaceAccount<'info,Mint>,
+    #[account(
+        mut,
+        seeds=[b"treasury_token_account"],
+        bump
+    )]
+    pub treasury_token_account:InterfaceAccount<'info,TokenAccount>,
+    // SOL/USD price, the other assets are priced through `remaining_accounts`
+    pub price_update:Account<'info, PriceUpdateV2>,
+    pub system_program:Program<'info,System>,
+    pub token_program_2022: Interface<'info, TokenInterface>,
+    pub associated_token_program:Program<'info,AssociatedToken>
+}
+
+    // 1. Check the health factor of the whole position with the new debt
+    // 2. Split the debt across the SPL balances and check each collateral type's debt ceiling
+    // 3. Mint tokens to the owner, less the mint fee
+    // 4. Updating position state
+
+pub fn process_mint_position(ctx:Context<MintPosition>, amount:u64) -> Result<()> {
+    require!(!ctx.accounts.config.is_paused(PAUSE_MINT_STABLE), ErrorCode::ProtocolPaused);
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    let config = &mut ctx.accounts.config;
+    config.accrue_interest()?;
+    let position = &mut ctx.accounts.position;
+
+    // 1. every asset is valued at the low end of its price
+    let value = value_position(position, config, &ctx.accounts.price_update, ctx.remaining_accounts, PriceBound::Lower)?;
+    let normalized_amount = config.normalize(amount, Rounding::Up)?;
+    let new_coins_balance = position.coins.checked_add(normalized_amount).ok_or(ErrorCode::MathOverflow)?;
+    config.add_debt(normalized_amount, new_coins_balance)?;
+    let new_debt = config.debt_of(new_coins_balance)?;
+    let health_factor = health_factor_from_weighted(new_debt, value.weighted_collateral)?;
+    if health_factor < config.min_health_factor_wad()? {
+        return Err(ErrorCode::HealthFactorError.into());
+    }
+
+    // 2. every SPL balance counts its share of the whole debt against its collateral type,
+    //    `remaining_accounts` holds each type ahead of its price update
+    let shares = position_debt_shares(new_coins_balance, &value)?;
+    assign_position_debt(position, config, ctx.remaining_accounts.iter().step_by(2), &shares)?;
+
+    // 3. mint tokens to the owner, less the mint fee which goes to the treasury
+    let mint_fee = mint_with_fee(
+        &ctx.accounts.owner_token_account,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.token_program_2022,
+        &ctx.accounts.mint,
+        config.bump_mint_acc,
+        amount,
+        config.mint_fee_bps
+    )?;
+
+    // 4. updating state
+    position.coins = new_coins_balance;
+    let protocol_state = &mut ctx.accounts.protocol_state;
+    protocol_state.record_fee(mint_fee)?;
+    protocol_state.sync_debt(config)?;
+
+    emit_cpi!(Minted {
+        owner: ctx.accounts.owner.key(),
+        position: position.key(),
+        amount,
+        fee: mint_fee,
+        debt: new_debt,
+        price: None,
+        health_factor,
+    });
+    Ok(())
+}
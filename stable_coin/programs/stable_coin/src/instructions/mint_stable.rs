@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface}};
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+use crate::{calculate_health_factor, error::ErrorCode, events::Minted, lamports_to_usd, math::Rounding, mint_with_fee, oracle_price, state::{Collateral, Config, ProtocolState}, PriceBound, PAUSE_MINT_STABLE};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MintStable<'info> {
+    #[account(mut)]
+    pub minter:Signer<'info>,
+    #[account(
+        mut,
+        seeds=[b"collateral", minter.key().as_ref()],
+        bump=collateral_account.bump
+    )]
+    pub collateral_account:Account<'info,Collateral>,
+    #[account(
+        init_if_needed,
+        payer=minter,
+        associated_token::mint=mint,
+        associated_token::authority=minter,
+        associated_token::token_program=token_program_2022
+    )]
+    pub minter_token_account:InterfaceAccount<'info,TokenAccount>,
+    #[account(
+        mut,
+        seeds=[b"config"],
+        bump=config.bump
+    )]
+    pub config:Account<'info,Config>,
+    #[account(
+        mut,
+        seeds=[b"protocol_state"],
+        bump=protocol_state.bump
+    )]
+    pub protocol_state:Account<'info,ProtocolState>,
+    #[account(
+        mut,
+        seeds=[b"jacked_nerd"],
+        mint::authority=mint,
+        mint::freeze_authority=mint,
+        mint::token_program=token_program_2022,
+        bump
+    )]
+    pub mint:InterfaceAccount<'info,Mint>,
+    #[account(
+        mut,
+        seeds=[b"treasury_token_account"],
+        bump
+    )]
+    pub treasury_token_account:InterfaceAccount<'info,TokenAccount>,
+    pub price_update:Account<'info, PriceUpdateV2>,
+    pub system_program:Program<'info,System>,
+    pub token_program_2022: Interface<'info, TokenInterface>,
+    pub associated_token_program:Program<'info,AssociatedToken>
+}
+
+    // 1. Check the health factor of the position with the new debt
+    // 2. Mint tokens to the user, less the mint fee
+    // 3. Updating user state
+
+pub fn process_mint_stable(ctx: Context<MintStable>, amount:u64) -> Result<()> {
+    require!(!ctx.accounts.config.is_paused(PAUSE_MINT_STABLE), ErrorCode::ProtocolPaused);
+    require!(!ctx.accounts.collateral_account.in_auction, ErrorCode::PositionInAuction);
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    let collateral = &mut ctx.accounts.collateral_account;
+    let config = &mut ctx.accounts.config;
+    config.accrue_interest()?;
+
+    // 1. collateral is valued at the low end of the confidence interval
+    let price_in_usd = oracle_price(&ctx.accounts.price_update, config, PriceBound::Lower)?;
+    let collateral_in_usd = lamports_to_usd(collateral.lamports, price_in_usd, Rounding::Down)?;
+    let normalized_amount = config.normalize(amount, Rounding::Up)?;
+    let new_coins_balance = collateral.coins.checked_add(normalized_amount).ok_or(ErrorCode::MathOverflow)?;
+    config.add_debt(normalized_amount, new_coins_balance)?;
+
+    let new_debt = config.debt_of(new_coins_balance)?;
+    let health_factor = calculate_health_factor(new_debt, collateral_in_usd, config.liq_thx)?;
+    if health_factor < config.min_health_factor_wad()? {
+        return Err(ErrorCode::HealthFactorError.into());
+    }
+
+    // 2. mint tokens to the user, less the mint fee which goes to the treasury
+    let mint_fee = mint_with_fee(
+        &ctx.accounts.minter_token_account,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.token_program_2022,
+        &ctx.accounts.mint,
+        config.bump_mint_acc,
+        amount,
+        config.mint_fee_bps
+    )?;
+
+    // 3. updating user state
+    collateral.coin_token_account = ctx.accounts.minter_token_account.key();
+    collateral.coins = new_coins_balance;
+    let protocol_state = &mut ctx.accounts.protocol_state;
+    protocol_state.record_fee(mint_fee)?;
+    protocol_state.sync_debt(config)?;
+
+    emit_cpi!(Minted {
+        owner: ctx.accounts.minter.key(),
+        position: collateral.key(),
+        amount,
+        fee: mint_fee,
+        debt: new_debt,
+        price: Some(price_in_usd),
+        health_factor,
+    });
+    Ok(())
+}
@@ -1,15 +1,132 @@
 pub mod deposit_mint;
 pub mod withdraw_burn;
 pub mod liquidate;
+pub mod repay;
+pub mod withdraw_collateral;
+pub mod deposit_collateral;
+pub mod mint_stable;
+pub mod close_position;
+pub mod get_position_health;
 
 pub use deposit_mint::*;
 pub use withdraw_burn::*;
 pub use liquidate::*;
+pub use repay::*;
+pub use withdraw_collateral::*;
+pub use deposit_collateral::*;
+pub use mint_stable::*;
+pub use close_position::*;
+pub use get_position_health::*;
 
 pub mod init_config;
 
 pub use init_config::*;
 
+pub mod update_config;
+
+pub use update_config::*;
+
 pub mod shared;
 
-pub use shared::*;
\ No newline at end of file
+pub use shared::*;
+pub mod propose_authority;
+pub mod accept_authority;
+pub mod renounce_authority;
+
+pub use propose_authority::*;
+pub use accept_authority::*;
+pub use renounce_authority::*;
+
+pub mod set_guardian;
+pub mod set_pause;
+
+pub use set_guardian::*;
+pub use set_pause::*;
+
+pub mod update_oracle;
+pub mod update_stability_fee;
+pub mod update_debt_ceilings;
+
+pub use update_oracle::*;
+pub use update_stability_fee::*;
+pub use update_debt_ceilings::*;
+
+pub mod init_treasury;
+pub mod update_fees;
+pub mod withdraw_treasury;
+pub mod withdraw_treasury_collateral;
+
+pub use init_treasury::*;
+pub use update_fees::*;
+pub use withdraw_treasury::*;
+pub use withdraw_treasury_collateral::*;
+
+pub mod add_collateral_type;
+pub mod deposit_token_collateral;
+pub mod withdraw_token_collateral;
+pub mod mint_against_token_collateral;
+pub mod repay_token_collateral;
+pub mod liquidate_token_collateral;
+
+pub use add_collateral_type::*;
+pub use deposit_token_collateral::*;
+pub use withdraw_token_collateral::*;
+pub use mint_against_token_collateral::*;
+pub use repay_token_collateral::*;
+pub use liquidate_token_collateral::*;
+
+pub mod open_position;
+pub mod deposit_position_sol;
+pub mod deposit_position_token;
+pub mod withdraw_position_sol;
+pub mod withdraw_position_token;
+pub mod mint_position;
+pub mod repay_position;
+pub mod liquidate_position_sol;
+pub mod liquidate_position_token;
+
+pub use open_position::*;
+pub use deposit_position_sol::*;
+pub use deposit_position_token::*;
+pub use withdraw_position_sol::*;
+pub use withdraw_position_token::*;
+pub use mint_position::*;
+pub use repay_position::*;
+pub use liquidate_position_sol::*;
+pub use liquidate_position_token::*;
+
+pub mod start_auction;
+pub mod bid_auction;
+pub mod update_auction_params;
+
+pub use start_auction::*;
+pub use bid_auction::*;
+pub use update_auction_params::*;
+
+pub mod init_stability_pool;
+pub mod deposit_stability_pool;
+pub mod withdraw_stability_pool;
+pub mod liquidate_with_pool;
+
+pub use init_stability_pool::*;
+pub use deposit_stability_pool::*;
+pub use withdraw_stability_pool::*;
+pub use liquidate_with_pool::*;
+
+pub mod settle_bad_debt;
+
+pub use settle_bad_debt::*;
+
+pub mod redeem;
+
+pub use redeem::*;
+
+pub mod init_psm;
+pub mod update_psm;
+pub mod swap_in;
+pub mod swap_out;
+
+pub use init_psm::*;
+pub use update_psm::*;
+pub use swap_in::*;
+pub use swap_out::*;
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::{events::PositionOpened, state::{Position, ProtocolState}};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct OpenPosition<'info> {
+    #[account(mut)]
+    pub owner:Signer<'info>,
+    #[account(
+        init,
+        payer=owner,
+        seeds=[b"position", owner.key().as_ref()],
+        space= 8 + Position::INIT_SPACE,
+        bump
+    )]
+    pub position:Account<'info,Position>,
+    #[account(
+        mut,
+        seeds=[b"protocol_state"],
+        bump=protocol_state.bump
+    )]
+    pub protocol_state:Account<'info,ProtocolState>,
+    /// SAFETY: This account is only used as a recipient for SOL transfers.
+    /// The seeds ensure that the PDA is derived deterministically and cannot be arbitrarily passed in by the client.
+    /// Created rent exempt so withdrawals and liquidations can leave any balance in it.
+    #[account(
+        init,
+        payer=owner,
+        seeds=[b"position_sol_account", owner.key().as_ref()],
+        space=0,
+        owner = System::id(),
+        bump
+    )]
+    pub position_sol_account:AccountInfo<'info>,
+    pub system_program:Program<'info,System>,
+}
+
+// Creates an empty cross-collateral position for the signer, along with its rent exempt SOL vault.
+pub fn process_open_position(ctx:Context<OpenPosition>) -> Result<()> {
+    let position = &mut ctx.accounts.position;
+    position.owner = ctx.accounts.owner.key();
+    position.bump = ctx.bumps.position;
+    position.bump_sol_account = ctx.bumps.position_sol_account;
+    ctx.accounts.protocol_state.open_position()?;
+
+    emit_cpi!(PositionOpened {
+        owner: ctx.accounts.owner.key(),
+        position: ctx.accounts.position.key(),
+        collateral_type: None,
+    });
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, events::AuthorityProposed, state::Config};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    pub authority:Signer<'info>,
+    #[account(
+        mut,
+        seeds=[b"config"],
+        bump=config.bump,
+        has_one=authority @ ErrorCode::Unauthorized
+    )]
+    pub config:Account<'info,Config>,
+}
+
+// The new authority only takes over once it signs `accept_authority`,
+// so proposing a wrong key can be undone by proposing again.
+pub fn process_propose_authority(ctx:Context<ProposeAuthority>, new_authority:Pubkey) -> Result<()> {
+    require!(new_authority != Pubkey::default(), ErrorCode::InvalidAuthority);
+
+    let config = &mut ctx.accounts.config;
+    config.pending_authority = Some(new_authority);
+
+    emit_cpi!(AuthorityProposed {
+        authority: config.authority,
+        pending_authority: new_authority,
+    });
+    Ok(())
+}
@@ -0,0 +1,166 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface}};
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+use crate::{burn_tokens, calculate_health_factor, error::ErrorCode, events::Redeemed, lamports_to_usd, load_account, math::{mul_div, to_u64, Rounding, WAD}, oracle_price, state::{Collateral, Config, ProtocolState, Treasury}, store_account, transfer_from_vault, usd_to_lamports, PriceBound, BASIS_POINTS, PAUSE_REDEEM};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Redeem<'info>{
+    #[account(mut)]
+    pub redeemer:Signer<'info>,
+    #[account(
+        mut,
+        associated_token::mint=mint,
+        associated_token::authority=redeemer,
+        associated_token::token_program=token_program_2022
+    )]
+    pub redeemer_token_account:InterfaceAccount<'info,TokenAccount>,
+    #[account(
+        mut,
+        seeds=[b"config"],
+        bump=config.bump
+    )]
+    pub config:Account<'info,Config>,
+    #[account(
+        mut,
+        seeds=[b"protocol_state"],
+        bump=protocol_state.bump
+    )]
+    pub protocol_state:Account<'info,ProtocolState>,
+    #[account(
+        mut,
+        seeds=[b"jacked_nerd"],
+        mint::authority=mint,
+        mint::freeze_authority=mint,
+        mint::token_program=token_program_2022,
+        bump
+    )]
+    pub mint:InterfaceAccount<'info,Mint>,
+    #[account(
+        mut,
+        seeds=[b"treasury"],
+        bump=treasury.bump
+    )]
+    pub treasury:Account<'info,Treasury>,
+    pub price_update:Account<'info,PriceUpdateV2>,
+    pub system_program:Program<'info,System>,
+    pub token_program_2022:Interface<'info,TokenInterface>,
+    pub associated_token_program:Program<'info,AssociatedToken>
+    // remaining accounts: (`Collateral`, its SOL vault) pairs, writable, by ascending health factor
+}
+
+//  1. Raise the redemption base rate for this volume and check the fee against the caller's limit
+//  2. Walk the positions from the lowest health factor up, rejecting any out of order. Positions
+//     without debt, already liquidatable or being auctioned are rejected rather than skipped:
+//     the latter two belong to the liquidators, and skipping them would still have to fit them
+//     into the ordering. Positions left once the amount is covered are not read.
+//  3. Repay each position's debt with the redeemed stablecoins and take $1 of its SOL per coin,
+//     less the fee which goes to the treasury
+//  4. Burn the redeemed stablecoins
+//
+// The program cannot see positions that are not passed in, so it only checks that the ones
+// given are in order; redeemers are expected to pass the lowest ones from an off-chain index.
+
+pub fn process_redeem<'info>(ctx:Context<'_, '_, 'info, 'info, Redeem<'info>>, amount:u64, max_fee_bps:u64) -> Result<()> {
+    require!(!ctx.accounts.config.is_paused(PAUSE_REDEEM), ErrorCode::ProtocolPaused);
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    // `is_multiple_of` is newer than the rustc shipped with the SBF toolchain
+    #[allow(clippy::manual_is_multiple_of)]
+    let paired = ctx.remaining_accounts.len() % 2 == 0;
+    require!(!ctx.remaining_accounts.is_empty() && paired, ErrorCode::InvalidRemainingAccounts);
+
+    let config = &mut ctx.accounts.config;
+    config.accrue_interest()?;
+
+    // 1. fee for this redemption
+    let fee_rate = config.update_redemption_rate(amount, ctx.accounts.mint.supply, Clock::get()?.unix_timestamp)?;
+    let max_fee_rate = mul_div(max_fee_bps as u128, WAD, BASIS_POINTS as u128, Rounding::Down)?;
+    require!(fee_rate <= max_fee_rate, ErrorCode::RedemptionFeeAboveLimit);
+
+    // collateral is paid out at the high end of the confidence interval
+    let price_in_usd = oracle_price(&ctx.accounts.price_update, config, PriceBound::Upper)?;
+
+    let mut remaining = amount;
+    let mut total_lamports:u64 = 0;
+    let mut total_fee:u64 = 0;
+    let mut positions:u8 = 0;
+    let mut previous_health_factor = 0;
+    for accounts in ctx.remaining_accounts.chunks(2) {
+        if remaining == 0 {
+            break;
+        }
+        let mut collateral = load_account::<Collateral>(&accounts[0])?;
+        require_keys_eq!(accounts[1].key(), collateral.sol_account, ErrorCode::InvalidRemainingAccounts);
+
+        // 2. only redeemable positions may be passed, and their health factors must not
+        //    decrease along the list
+        let debt = config.debt_of(collateral.coins)?;
+        let collateral_in_usd = lamports_to_usd(collateral.lamports, price_in_usd, Rounding::Down)?;
+        let health_factor = calculate_health_factor(debt, collateral_in_usd, config.liq_thx)?;
+        require!(debt > 0 && health_factor >= WAD && !collateral.in_auction, ErrorCode::PositionNotRedeemable);
+        require!(health_factor >= previous_health_factor, ErrorCode::RedemptionNotSorted);
+        previous_health_factor = health_factor;
+
+        // 3. redeem against this position
+        let redeemed = remaining.min(debt);
+        let lamports = usd_to_lamports(redeemed, price_in_usd, Rounding::Down)?.min(collateral.lamports);
+        let fee = to_u64(mul_div(lamports as u128, fee_rate, WAD, Rounding::Up)?)?;
+        let repaid_coins = config.normalized_repayment(collateral.coins, redeemed)?;
+
+        transfer_from_vault(
+            &ctx.accounts.system_program,
+            &accounts[1],
+            &ctx.accounts.redeemer.to_account_info(),
+            &collateral.depositer,
+            collateral.bump_sol_account,
+            lamports - fee
+        )?;
+        if fee > 0 {
+            transfer_from_vault(
+                &ctx.accounts.system_program,
+                &accounts[1],
+                &ctx.accounts.treasury.to_account_info(),
+                &collateral.depositer,
+                collateral.bump_sol_account,
+                fee
+            )?;
+        }
+
+        collateral.coins = collateral.coins.checked_sub(repaid_coins).ok_or(ErrorCode::MathOverflow)?;
+        collateral.lamports -= lamports;
+        config.remove_debt(repaid_coins)?;
+        store_account(&accounts[0], &collateral)?;
+
+        remaining -= redeemed;
+        total_lamports = total_lamports.checked_add(lamports).ok_or(ErrorCode::MathOverflow)?;
+        total_fee = total_fee.checked_add(fee).ok_or(ErrorCode::MathOverflow)?;
+        positions += 1;
+    }
+    require!(remaining == 0, ErrorCode::InsufficientRedeemableDebt);
+
+    // 4. burn what was redeemed
+    burn_tokens(
+        &ctx.accounts.mint,
+        &ctx.accounts.token_program_2022,
+        &ctx.accounts.redeemer_token_account,
+        amount,
+        &ctx.accounts.redeemer
+    )?;
+
+    let protocol_state = &mut ctx.accounts.protocol_state;
+    protocol_state.remove_collateral(total_lamports)?;
+    protocol_state.record_fee(lamports_to_usd(total_fee, price_in_usd, Rounding::Down)?)?;
+    protocol_state.sync_debt(config)?;
+
+    emit_cpi!(Redeemed {
+        redeemer: ctx.accounts.redeemer.key(),
+        amount,
+        lamports: total_lamports,
+        fee: total_fee,
+        fee_rate,
+        price: price_in_usd,
+        positions,
+    });
+    Ok(())
+}
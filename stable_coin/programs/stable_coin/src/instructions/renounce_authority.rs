@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, events::AuthorityRenounced, state::Config};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RenounceAuthority<'info> {
+    pub authority:Signer<'info>,
+    #[account(
+        mut,
+        seeds=[b"config"],
+        bump=config.bump,
+        has_one=authority @ ErrorCode::Unauthorized
+    )]
+    pub config:Account<'info,Config>,
+}
+
+// Sets the authority to the default public key, which nobody can sign for.
+// This is irreversible: every governance instruction is disabled afterwards,
+// so the guardian goes with it and nothing may be paused at the time.
+pub fn process_renounce_authority(ctx:Context<RenounceAuthority>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let old_authority = config.authority;
+
+    config.renounce_authority()?;
+
+    emit_cpi!(AuthorityRenounced { old_authority });
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface}};
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+use crate::{burn_tokens, calculate_health_factor, close_auction, error::ErrorCode, events::{AuctionSettled, Repaid}, lamports_to_usd, math::{Rounding, WAD}, oracle_price, state::{Auction, Collateral, Config, ProtocolState}, PriceBound, PAUSE_REPAY};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Repay<'info>{
+    #[account(mut)]
+    pub repayer:Signer<'info>,
+    #[account(
+        mut,
+        seeds=[b"collateral", repayer.key().as_ref()],
+        bump=collateral_account.bump
+    )]
+    pub collateral_account:Account<'info,Collateral>,
+    #[account(
+        mut,
+        seeds=[b"config"],
+        bump=config.bump
+    )]
+    pub config:Account<'info,Config>,
+    #[account(
+        mut,
+        seeds=[b"protocol_state"],
+        bump=protocol_state.bump
+    )]
+    pub protocol_state:Account<'info,ProtocolState>,
+    #[account(
+        mut,
+        associated_token::mint=mint,
+        associated_token::authority=repayer,
+        associated_token::token_program=token_program
+    )]
+    pub repayer_token_account:InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds=[b"jacked_nerd"],
+        mint::authority=mint,
+        mint::freeze_authority=mint,
+        mint::token_program=token_program,
+        bump
+    )]
+    pub mint:InterfaceAccount<'info,Mint>,
+    // only needed while the position is in auction, a repayment that restores
+    // its health ends the auction and returns the rent to the keeper
+    #[account(
+        mut,
+        seeds=[b"auction", collateral_account.key().as_ref()],
+        bump=auction.bump
+    )]
+    pub auction:Option<Account<'info,Auction>>,
+    /// SAFETY: checked against `auction.keeper`, only receives the auction account's rent.
+    #[account(mut)]
+    pub keeper:Option<AccountInfo<'info>>,
+    pub price_update:Option<Account<'info,PriceUpdateV2>>,
+    pub token_program:Interface<'info, TokenInterface>,
+    pub associated_token_program:Program<'info,AssociatedToken>
+}
+
+//  1. Make sure the user is not repaying more than they owe
+//  2. Burn the repaid stablecoins
+//  3. Reduce the user's debt
+//  4. End the auction of a position that is healthy again
+//
+// Repaying can only raise the health factor, so unlike the withdrawals this does
+// not read the oracle and keeps working while prices are stale. Only a position in
+// auction is priced, to find out whether the repayment ended it.
+
+pub fn process_repay(ctx:Context<Repay>, amount:u64) -> Result<()> {
+    require!(!ctx.accounts.config.is_paused(PAUSE_REPAY), ErrorCode::ProtocolPaused);
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    let collateral_account = &mut ctx.accounts.collateral_account;
+    let config = &mut ctx.accounts.config;
+    config.accrue_interest()?;
+
+    // 1. check the outstanding debt, including the accrued stability fee
+    let repaid_coins = config.normalized_repayment(collateral_account.coins, amount)?;
+
+    // 2. burn the repaid tokens
+    burn_tokens(
+        &ctx.accounts.mint,
+        &ctx.accounts.token_program,
+        &ctx.accounts.repayer_token_account,
+        amount,
+        &ctx.accounts.repayer
+    )?;
+
+    // 3. update the user's debt
+    collateral_account.coins = collateral_account.coins.checked_sub(repaid_coins).ok_or(ErrorCode::MathOverflow)?;
+    config.remove_debt(repaid_coins)?;
+    ctx.accounts.protocol_state.sync_debt(config)?;
+
+    let debt = config.debt_of(collateral_account.coins)?;
+    emit_cpi!(Repaid {
+        payer: ctx.accounts.repayer.key(),
+        position: collateral_account.key(),
+        amount,
+        debt,
+    });
+
+    // 4. judged at the same price as `start_auction`, a position that would not be auctioned ends its auction
+    if collateral_account.in_auction {
+        let (Some(auction), Some(keeper), Some(price_update)) = (&ctx.accounts.auction, &ctx.accounts.keeper, &ctx.accounts.price_update) else {
+            return Err(ErrorCode::PositionInAuction.into());
+        };
+        require_keys_eq!(keeper.key(), auction.keeper, ErrorCode::PositionInAuction);
+        let price_in_usd = oracle_price(price_update, config, PriceBound::Upper)?;
+        let collateral_in_usd = lamports_to_usd(collateral_account.lamports, price_in_usd, Rounding::Down)?;
+        if calculate_health_factor(debt, collateral_in_usd, config.liq_thx)? >= WAD {
+            close_auction(collateral_account, auction, keeper)?;
+            emit_cpi!(AuctionSettled {
+                position: collateral_account.key(),
+                auction: auction.key(),
+                surplus: 0,
+                remaining_debt: debt,
+            });
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface}};
+
+use crate::{assign_position_debt, burn_tokens, error::ErrorCode, events::Repaid, repaid_position_debt_shares, state::{Config, Position, ProtocolState}, PAUSE_REPAY};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RepayPosition<'info>{
+    #[account(mut)]
+    pub owner:Signer<'info>,
+    #[account(
+        mut,
+        seeds=[b"config"],
+        bump=config.bump
+    )]
+    pub config:Account<'info,Config>,
+    #[account(
+        mut,
+        seeds=[b"protocol_state"],
+        bump=protocol_state.bump
+    )]
+    pub protocol_state:Account<'info,ProtocolState>,
+    #[account(
+        mut,
+        seeds=[b"position", owner.key().as_ref()],
+        bump=position.bump
+    )]
+    pub position:Account<'info,Position>,
+    #[account(
+        mut,
+        associated_token::mint=mint,
+        associated_token::authority=owner,
+        associated_token::token_program=token_program
+    )]
+    pub owner_token_account:InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds=[b"jacked_nerd"],
+        mint::authority=mint,
+        mint::freeze_authority=mint,
+        mint::token_program=token_program,
+        bump
+    )]
+    pub mint:InterfaceAccount<'info,Mint>,
+    pub token_program:Interface<'info, TokenInterface>,
+    pub associated_token_program:Program<'info,AssociatedToken>
+}
+
+//  1. Make sure the owner is not repaying more than they owe
+//  2. Burn the repaid stablecoins
+//  3. Reduce the position's debt and release the same part of each SPL balance's share of it from
+//     its collateral type, `remaining_accounts` holds the `CollateralType` of every balance in order
+
+pub fn process_repay_position(ctx:Context<RepayPosition>, amount:u64) -> Result<()> {
+    require!(!ctx.accounts.config.is_paused(PAUSE_REPAY), ErrorCode::ProtocolPaused);
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    let position = &mut ctx.accounts.position;
+    let config = &mut ctx.accounts.config;
+    config.accrue_interest()?;
+
+    // 1. check the outstanding debt, including the accrued stability fee
+    let repaid_coins = config.normalized_repayment(position.coins, amount)?;
+
+    // 2. burn the repaid tokens
+    burn_tokens(
+        &ctx.accounts.mint,
+        &ctx.accounts.token_program,
+        &ctx.accounts.owner_token_account,
+        amount,
+        &ctx.accounts.owner
+    )?;
+
+    // 3. update the debt
+    let shares = repaid_position_debt_shares(position, repaid_coins)?;
+    assign_position_debt(position, config, ctx.remaining_accounts.iter(), &shares)?;
+    position.coins = position.coins.checked_sub(repaid_coins).ok_or(ErrorCode::MathOverflow)?;
+    config.remove_debt(repaid_coins)?;
+    ctx.accounts.protocol_state.sync_debt(config)?;
+
+    let debt = config.debt_of(position.coins)?;
+    emit_cpi!(Repaid {
+        payer: ctx.accounts.owner.key(),
+        position: position.key(),
+        amount,
+        debt,
+    });
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface}};
+
+use crate::{burn_tokens, error::ErrorCode, events::Repaid, state::{CollateralType, Config, ProtocolState, TokenCollateral}, PAUSE_REPAY};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RepayTokenCollateral<'info>{
+    #[account(mut)]
+    pub repayer:Signer<'info>,
+    #[account(
+        mut,
+        seeds=[b"config"],
+        bump=config.bump
+    )]
+    pub config:Account<'info,Config>,
+    #[account(
+        mut,
+        seeds=[b"protocol_state"],
+        bump=protocol_state.bump
+    )]
+    pub protocol_state:Account<'info,ProtocolState>,
+    #[account(
+        mut,
+        seeds=[b"collateral_type", collateral_type.mint.as_ref()],
+        bump=collateral_type.bump
+    )]
+    pub collateral_type:Account<'info,CollateralType>,
+    #[account(
+        mut,
+        seeds=[b"collateral", repayer.key().as_ref(), collateral_type.key().as_ref()],
+        bump=token_collateral.bump
+    )]
+    pub token_collateral:Account<'info,TokenCollateral>,
+    #[account(
+        mut,
+        associated_token::mint=mint,
+        associated_token::authority=repayer,
+        associated_token::token_program=token_program
+    )]
+    pub repayer_token_account:InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds=[b"jacked_nerd"],
+        mint::authority=mint,
+        mint::freeze_authority=mint,
+        mint::token_program=token_program,
+        bump
+    )]
+    pub mint:InterfaceAccount<'info,Mint>,
+    pub token_program:Interface<'info, TokenInterface>,
+    pub associated_token_program:Program<'info,AssociatedToken>
+}
+
+//  1. Make sure the user is not repaying more than they owe
+//  2. Burn the repaid stablecoins
+//  3. Reduce the user's and the collateral type's debt
+
+pub fn process_repay_token_collateral(ctx:Context<RepayTokenCollateral>, amount:u64) -> Result<()> {
+    require!(!ctx.accounts.config.is_paused(PAUSE_REPAY), ErrorCode::ProtocolPaused);
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    let position = &mut ctx.accounts.token_collateral;
+    let collateral_type = &mut ctx.accounts.collateral_type;
+    let config = &mut ctx.accounts.config;
+    config.accrue_interest()?;
+
+    // 1. check the outstanding debt, including the accrued stability fee
+    let repaid_coins = config.normalized_repayment(position.coins, amount)?;
+
+    // 2. burn the repaid tokens
+    burn_tokens(
+        &ctx.accounts.mint,
+        &ctx.accounts.token_program,
+        &ctx.accounts.repayer_token_account,
+        amount,
+        &ctx.accounts.repayer
+    )?;
+
+    // 3. update the debt
+    position.coins = position.coins.checked_sub(repaid_coins).ok_or(ErrorCode::MathOverflow)?;
+    config.remove_debt(repaid_coins)?;
+    collateral_type.total_debt = collateral_type.total_debt.checked_sub(repaid_coins).ok_or(ErrorCode::MathOverflow)?;
+    ctx.accounts.protocol_state.sync_debt(config)?;
+
+    let debt = config.debt_of(position.coins)?;
+    emit_cpi!(Repaid {
+        payer: ctx.accounts.repayer.key(),
+        position: position.key(),
+        amount,
+        debt,
+    });
+    Ok(())
+}
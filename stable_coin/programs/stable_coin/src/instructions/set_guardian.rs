@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, events::GuardianUpdated, state::Config};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    pub authority:Signer<'info>,
+    #[account(
+        mut,
+        seeds=[b"config"],
+        bump=config.bump,
+        has_one=authority @ ErrorCode::Unauthorized
+    )]
+    pub config:Account<'info,Config>,
+}
+
+// Passing `None` removes the guardian, leaving pausing to the authority alone.
+pub fn process_set_guardian(ctx:Context<SetGuardian>, guardian:Option<Pubkey>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let old_guardian = config.guardian;
+    config.guardian = guardian;
+
+    emit_cpi!(GuardianUpdated {
+        old_guardian,
+        new_guardian: guardian,
+    });
+    Ok(())
+}
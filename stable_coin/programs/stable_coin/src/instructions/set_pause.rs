@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, events::PauseUpdated, state::Config, PAUSE_ALL};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+    pub signer:Signer<'info>,
+    #[account(
+        mut,
+        seeds=[b"config"],
+        bump=config.bump,
+        constraint = signer.key() == config.authority
+            || Some(signer.key()) == config.guardian @ ErrorCode::Unauthorized
+    )]
+    pub config:Account<'info,Config>,
+}
+
+// 1. Reject unknown bits
+// 2. The guardian may only add flags, lifting a pause is left to the authority
+// 3. Store the new flags
+
+pub fn process_set_pause(ctx:Context<SetPause>, paused_flags:u32) -> Result<()> {
+    // 1. reject unknown bits
+    require!(paused_flags & !PAUSE_ALL == 0, ErrorCode::InvalidPauseFlags);
+
+    let config = &mut ctx.accounts.config;
+    let signer = ctx.accounts.signer.key();
+    let old_paused_flags = config.paused_flags;
+
+    // 2. guardian cannot clear a flag that is currently set
+    if signer != config.authority {
+        require!(
+            paused_flags & old_paused_flags == old_paused_flags,
+            ErrorCode::GuardianCannotUnpause
+        );
+    }
+
+    // 3. store the new flags
+    config.paused_flags = paused_flags;
+
+    emit_cpi!(PauseUpdated {
+        signer,
+        old_paused_flags,
+        new_paused_flags: paused_flags,
+    });
+    Ok(())
+}
@@ -0,0 +1,157 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{token_2022::{burn, Burn}, token_interface::{Mint, TokenAccount, TokenInterface}};
+
+use crate::{error::ErrorCode, events::BadDebtSettled, mint_surplus, state::{Collateral, CollateralType, Config, Position, ProtocolState, StabilityPool, TokenCollateral, Treasury}, STABILITY_POOL_SEED, TREASURY_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SettleBadDebt<'info> {
+    pub authority:Signer<'info>,
+    #[account(
+        mut,
+        seeds=[b"config"],
+        bump=config.bump,
+        has_one=authority @ ErrorCode::Unauthorized
+    )]
+    pub config:Account<'info,Config>,
+    #[account(
+        mut,
+        seeds=[b"protocol_state"],
+        bump=protocol_state.bump
+    )]
+    pub protocol_state:Account<'info,ProtocolState>,
+    // exactly one of the three kinds of position is written off, a `TokenCollateral`
+    // comes with its collateral type
+    #[account(mut)]
+    pub collateral_account:Option<Account<'info,Collateral>>,
+    #[account(mut)]
+    pub token_collateral:Option<Account<'info,TokenCollateral>>,
+    #[account(mut)]
+    pub collateral_type:Option<Account<'info,CollateralType>>,
+    #[account(mut)]
+    pub position:Option<Account<'info,Position>>,
+    #[account(
+        seeds=[b"treasury"],
+        bump=treasury.bump
+    )]
+    pub treasury:Account<'info,Treasury>,
+    #[account(
+        mut,
+        address=treasury.token_account
+    )]
+    pub treasury_token_account:InterfaceAccount<'info,TokenAccount>,
+    // only needed with `socialize`
+    #[account(
+        mut,
+        seeds=[b"stability_pool"],
+        bump=stability_pool.bump
+    )]
+    pub stability_pool:Option<Account<'info,StabilityPool>>,
+    #[account(mut)]
+    pub stability_pool_token_account:Option<InterfaceAccount<'info,TokenAccount>>,
+    #[account(
+        mut,
+        seeds=[b"jacked_nerd"],
+        bump=config.bump_mint_acc
+    )]
+    pub mint:InterfaceAccount<'info,Mint>,
+    pub token_program:Interface<'info,TokenInterface>,
+}
+
+//  1. Write off the debt of a `Collateral`, `TokenCollateral` or `Position` whose collateral is gone
+//  2. Cover as much of the protocol's bad debt as possible by burning treasury stablecoins,
+//     the booked stability fee surplus included
+//  3. With `socialize`, burn what is still uncovered out of the stability pool, cutting every
+//     deposit pro-rata without any collateral in return
+//  4. Whatever is left stays on `ProtocolState.bad_debt`
+
+pub fn process_settle_bad_debt(ctx:Context<SettleBadDebt>, socialize:bool) -> Result<()> {
+    let accounts = &mut *ctx.accounts;
+    accounts.config.accrue_interest()?;
+
+    // 1. write the position off, it must have no collateral left
+    let (position, normalized_debt) = match (&mut accounts.collateral_account, &mut accounts.token_collateral, &mut accounts.position) {
+        (Some(collateral), None, None) => {
+            require!(!collateral.in_auction, ErrorCode::PositionInAuction);
+            require!(collateral.lamports == 0, ErrorCode::PositionHasCollateral);
+            (collateral.key(), std::mem::take(&mut collateral.coins))
+        }
+        (None, Some(token_collateral), None) => {
+            let collateral_type = accounts.collateral_type.as_mut().ok_or(ErrorCode::InvalidBadDebtPosition)?;
+            require_keys_eq!(token_collateral.collateral_type, collateral_type.key(), ErrorCode::InvalidBadDebtPosition);
+            require!(token_collateral.amount == 0, ErrorCode::PositionHasCollateral);
+            collateral_type.total_debt = collateral_type.total_debt.checked_sub(token_collateral.coins).ok_or(ErrorCode::MathOverflow)?;
+            (token_collateral.key(), std::mem::take(&mut token_collateral.coins))
+        }
+        (None, None, Some(position)) => {
+            // no SPL balance is left, so none of the debt is still counted against a collateral type
+            require!(position.lamports == 0 && position.balances.is_empty(), ErrorCode::PositionHasCollateral);
+            (position.key(), std::mem::take(&mut position.coins))
+        }
+        _ => return Err(ErrorCode::InvalidBadDebtPosition.into()),
+    };
+    require!(normalized_debt > 0, ErrorCode::NoBadDebt);
+
+    let config = &mut accounts.config;
+    let debt = config.debt_of(normalized_debt)?;
+    config.remove_debt(normalized_debt)?;
+
+    let protocol_state = &mut accounts.protocol_state;
+    protocol_state.record_bad_debt(debt)?;
+
+    // 2. treasury stablecoins are burned first, signed for by the treasury PDA, after minting
+    //    the stability fees booked since the last settlement into it
+    mint_surplus(config, protocol_state, &accounts.treasury_token_account, &accounts.token_program, &accounts.mint)?;
+    accounts.treasury_token_account.reload()?;
+    let covered = protocol_state.bad_debt.min(accounts.treasury_token_account.amount);
+    if covered > 0 {
+        let signer_seeds:&[&[&[u8]]] = &[&[TREASURY_SEED, &[accounts.treasury.bump]]];
+        let context = CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            Burn {
+                authority:accounts.treasury.to_account_info(),
+                mint:accounts.mint.to_account_info(),
+                from:accounts.treasury_token_account.to_account_info(),
+            },
+            signer_seeds);
+        burn(context, covered)?;
+        protocol_state.cover_bad_debt(covered)?;
+    }
+
+    // 3. the rest is optionally taken from the stability pool as a loss with no collateral gain
+    let mut socialized = 0;
+    if socialize {
+        let (Some(pool), Some(pool_token_account)) = (&mut accounts.stability_pool, &accounts.stability_pool_token_account) else {
+            return Err(ErrorCode::MissingStabilityPool.into());
+        };
+        require_keys_eq!(pool_token_account.key(), pool.token_account, ErrorCode::MissingStabilityPool);
+        socialized = protocol_state.bad_debt.min(pool.total_deposits);
+        if socialized > 0 {
+            pool.offset(socialized, 0)?;
+            let signer_seeds:&[&[&[u8]]] = &[&[STABILITY_POOL_SEED, &[pool.bump]]];
+            let context = CpiContext::new_with_signer(
+                accounts.token_program.to_account_info(),
+                Burn {
+                    authority:pool.to_account_info(),
+                    mint:accounts.mint.to_account_info(),
+                    from:pool_token_account.to_account_info(),
+                },
+                signer_seeds);
+            burn(context, socialized)?;
+            protocol_state.cover_bad_debt(socialized)?;
+        }
+    }
+
+    // 4. anything left stays recorded as bad debt
+    protocol_state.sync_debt(config)?;
+
+    emit_cpi!(BadDebtSettled {
+        authority: ctx.accounts.authority.key(),
+        position,
+        debt,
+        covered,
+        socialized,
+        bad_debt: ctx.accounts.protocol_state.bad_debt,
+    });
+    Ok(())
+}
@@ -1,7 +1,8 @@
-use anchor_lang::prelude::*;
-use anchor_spl::{token_2022::{ burn, mint_to, Burn, MintTo }, token_interface::{Mint, TokenAccount, TokenInterface}};
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+use anchor_spl::{token_2022::{ burn, mint_to, Burn, MintTo }, token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked}};
+use pyth_solana_receiver_sdk::price_update::{Price, PriceUpdateV2};
 
-use crate::MINTSEED;
+use crate::{error::ErrorCode, math::{mul_div, price_to_wad, rescale, to_u64, Rounding, WAD}, state::{Auction, Collateral, CollateralType, Config, Position, PriceMode, ProtocolState}, BASIS_POINTS, COLLATERAL_TYPE_SEED, COLLATERAL_VAULT_SEED, MINTSEED, POSITION_VAULT_SEED, STABLE_DECIMALS};
 
 
 
@@ -14,7 +15,6 @@ pub fn mint_tokens<'info>(
 ) -> Result<()> {
 
     let signer_seeds: &[&[&[u8]]] = &[&[MINTSEED, &[mint_bump]]];
-    let scaled_token_amount = amount.checked_mul(1000000000).unwrap();
     let ctx = CpiContext::new_with_signer(
         token_program.to_account_info(),
         MintTo{
@@ -23,54 +23,576 @@ pub fn mint_tokens<'info>(
         mint:mint_address.to_account_info()
     }, signer_seeds);
 
-    mint_to(ctx, scaled_token_amount)?;
+    mint_to(ctx, amount)?;
 
     Ok(())
 }
 
+// Mints `amount` of new debt, `fee_bps` of it into the treasury and the rest to `to`.
+// Every minting path charges its fee through here, the fee is returned for the protocol stats.
+pub fn mint_with_fee<'info>(
+    to:&InterfaceAccount<'info,TokenAccount>,
+    treasury_token_account:&InterfaceAccount<'info,TokenAccount>,
+    token_program:&Interface<'info, TokenInterface>,
+    mint_address:&InterfaceAccount<'info, Mint>,
+    mint_bump:u8,
+    amount:u64,
+    fee_bps:u64
+) -> Result<u64> {
+    let fee = fee_amount(amount, fee_bps)?;
+    mint_tokens(to, token_program, mint_address, mint_bump, amount - fee)?;
+    if fee > 0 {
+        mint_tokens(treasury_token_account, token_program, mint_address, mint_bump, fee)?;
+    }
+    Ok(fee)
+}
 
-pub fn integer_usd_from_pyth(price_raw: i64, expo: i32) -> i128 {
-    let p = price_raw as i128;
-    if expo < 0 {
-        let denom = 10i128.pow((-expo) as u32);
-        p / denom      // integer part (floor for positive prices)
-    } else {
-        // expo >= 0 -> multiply
-        let mul = 10i128.pow(expo as u32);
-        p * mul
+
+// Mints the stability fees booked on `Config.surplus` into the treasury, so the treasury
+// holds the interest borrowers pay instead of it only being burned on repayment.
+pub fn mint_surplus<'info>(
+    config:&mut Config,
+    protocol_state:&mut ProtocolState,
+    treasury_token_account:&InterfaceAccount<'info,TokenAccount>,
+    token_program:&Interface<'info, TokenInterface>,
+    mint_address:&InterfaceAccount<'info, Mint>,
+) -> Result<u64> {
+    let surplus = config.take_surplus();
+    if surplus > 0 {
+        mint_tokens(treasury_token_account, token_program, mint_address, config.bump_mint_acc, surplus)?;
+        protocol_state.record_fee(surplus)?;
     }
+    Ok(surplus)
+}
+
+
+// Checks the risk parameters before they are written to the config account.
+// All values are in basis points, the minimum health factor can not go below 1.0, and at the liquidation threshold the collateral
+// must still be able to cover the repaid debt plus the liquidation bonus.
+pub fn validate_risk_params(
+    liq_thx:u64,
+    liq_bonus:u64,
+    min_health_factor:u64,
+    close_factor:u64,
+) -> Result<()> {
+    require!(close_factor > 0 && close_factor <= BASIS_POINTS, ErrorCode::InvalidCloseFactor);
+    require!(min_health_factor >= BASIS_POINTS, ErrorCode::InvalidMinHealthFactor);
+    validate_liquidation_params(liq_thx, liq_bonus)
+}
+
+// Threshold and bonus checks shared by the config and every collateral type.
+pub fn validate_liquidation_params(liq_thx:u64, liq_bonus:u64) -> Result<()> {
+    require!(liq_thx > 0 && liq_thx <= BASIS_POINTS, ErrorCode::InvalidLiquidationThreshold);
+
+    // (1 + bonus) * threshold <= 1
+    let bonus_headroom = (BASIS_POINTS as u128)
+        .checked_add(liq_bonus as u128)
+        .and_then(|v| v.checked_mul(liq_thx as u128));
+    require!(
+        matches!(bonus_headroom, Some(v) if v <= (BASIS_POINTS as u128) * (BASIS_POINTS as u128)),
+        ErrorCode::InvalidLiquidationBonus
+    );
+    Ok(())
+}
+
+// Reads the price from the update account after checking it against the expected feed id
+// and the staleness window stored in the config.
+pub fn fetch_price(price_update:&PriceUpdateV2, config:&Config, feed_id:&[u8;32]) -> Result<Price> {
+    require!(price_update.price_message.feed_id == *feed_id, ErrorCode::InvalidPriceFeed);
+
+    let clock = Clock::get()?;
+    let price = price_update.get_price_no_older_than(&clock, config.max_price_age, feed_id)?;
+    check_confidence(&price, config)?;
+    Ok(price)
 }
 
-pub fn lamports_to_usd<'info>(
+// Same checks as `fetch_price`, but returns the EMA price and confidence of the update.
+pub fn fetch_ema_price(price_update:&PriceUpdateV2, config:&Config, feed_id:&[u8;32]) -> Result<Price> {
+    let spot = fetch_price(price_update, config, feed_id)?;
+    let ema = Price {
+        price: price_update.price_message.ema_price,
+        conf: price_update.price_message.ema_conf,
+        exponent: spot.exponent,
+        publish_time: spot.publish_time,
+    };
+    check_confidence(&ema, config)?;
+    Ok(ema)
+}
+
+fn check_confidence(price:&Price, config:&Config) -> Result<()> {
+    // conf / price <= max_conf_bps / 10000
+    require!(price.price > 0, ErrorCode::InvalidPrice);
+    require!(
+        (price.conf as u128) * (BASIS_POINTS as u128) <= (price.price as u128) * (config.max_conf_bps as u128),
+        ErrorCode::PriceTooUncertain
+    );
+    Ok(())
+}
+
+// Which end of the pyth confidence interval a price is taken from.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PriceBound {
+    // price - conf, used when valuing collateral for minting or withdrawing
+    Lower,
+    // price + conf, used when judging liquidations and when paying out collateral for stablecoins
+    Upper,
+}
+
+pub fn bounded_price(price:&Price, bound:PriceBound) -> i64 {
+    // conf is below the price once `check_confidence` has run
+    let conf = price.conf as i64;
+    match bound {
+        PriceBound::Lower => price.price.saturating_sub(conf),
+        PriceBound::Upper => price.price.saturating_add(conf),
+    }
+}
+
+// WAD-scaled USD price of one SOL for the given bound. With `PriceMode::SpotAndEma` the spot and
+// EMA prices are both bounded and the one on the same side as the bound is kept, the
+// conservative price for the protocol in each operation: the lower for minting and
+// withdrawing, the higher for liquidations and redemptions. A single slot wick can then
+// neither over-mint, liquidate a position nor pay out too much collateral on its own.
+pub fn oracle_price(price_update:&PriceUpdateV2, config:&Config, bound:PriceBound) -> Result<u128> {
+    feed_price(price_update, config, &config.feed_id, bound)
+}
+
+// Same as `oracle_price` for any feed, e.g. the one stored on a `CollateralType`.
+pub fn feed_price(
+    price_update:&PriceUpdateV2,
+    config:&Config,
+    feed_id:&[u8;32],
+    bound:PriceBound,
+) -> Result<u128> {
+    let spot = fetch_price(price_update, config, feed_id)?;
+    let mut price = bounded_price(&spot, bound);
+
+    if config.price_mode == PriceMode::SpotAndEma {
+        let ema = bounded_price(&fetch_ema_price(price_update, config, feed_id)?, bound);
+        price = match bound {
+            PriceBound::Lower => price.min(ema),
+            PriceBound::Upper => price.max(ema),
+        };
+    }
+    price_to_wad(price, spot.exponent)
+}
+
+// Stablecoin base units worth `lamports` at `usd_per_sol` (WAD). Both SOL and the
+// stablecoin use 9 decimals, so no further scaling is needed.
+pub fn lamports_to_usd(
     lamports:u64,
-    usd:u64,
-) -> Result<(u64)>{
-    let token_amount = lamports.checked_mul(usd).unwrap().checked_div(1000000000).unwrap();
-    Ok(token_amount)
+    usd_per_sol:u128,
+    rounding:Rounding,
+) -> Result<u64>{
+    let token_amount = mul_div(lamports as u128, usd_per_sol, WAD, rounding)?;
+    to_u64(token_amount)
+}
+
+// Stablecoin base units worth `amount` base units of a token with `decimals`,
+// at `usd_per_token` (WAD) per whole token.
+pub fn token_to_usd(
+    amount:u64,
+    decimals:u8,
+    usd_per_token:u128,
+    rounding:Rounding,
+) -> Result<u64>{
+    let value = mul_div(amount as u128, usd_per_token, WAD, rounding)?;
+    to_u64(rescale(value, decimals, STABLE_DECIMALS, rounding)?)
+}
+
+// Base units of a token with `decimals` worth `usd_amount` stablecoin base units.
+pub fn usd_to_token(
+    usd_amount:u64,
+    decimals:u8,
+    usd_per_token:u128,
+    rounding:Rounding,
+) -> Result<u64>{
+    let value = rescale(usd_amount as u128, STABLE_DECIMALS, decimals, rounding)?;
+    to_u64(mul_div(value, WAD, usd_per_token, rounding)?)
 }
 
-pub fn usd_to_lamports<'info>(
+// Lamports worth `usd_amount` stablecoin base units at `usd_per_sol` (WAD).
+pub fn usd_to_lamports(
     usd_amount:u64,
-    usd_per_sol:u64
+    usd_per_sol:u128,
+    rounding:Rounding,
 )-> Result<u64>{
-     let lamports = usd_amount.checked_mul(1000000000).unwrap().checked_div(usd_per_sol).unwrap();
-     Ok((lamports))
+    let lamports = mul_div(usd_amount as u128, WAD, usd_per_sol, rounding)?;
+    to_u64(lamports)
+}
+
+// Share of `amount` owed to the treasury for a fee in basis points, rounded down.
+pub fn fee_amount(amount:u64, fee_bps:u64) -> Result<u64> {
+    to_u64(mul_div(amount as u128, fee_bps as u128, BASIS_POINTS as u128, Rounding::Down)?)
+}
+
+// Collateral a liquidation takes out of one balance, in base units of that collateral.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LiquidationAmounts {
+    // paid to the liquidator, the repaid value plus the bonus
+    pub seized:u64,
+    // the protocol's cut, taken from the borrower on top of `seized`
+    pub fee:u64,
+}
+
+impl LiquidationAmounts {
+    pub fn total(&self) -> Result<u64> {
+        Ok(self.seized.checked_add(self.fee).ok_or(ErrorCode::MathOverflow)?)
+    }
 }
 
+// Splits the collateral owed for `repaid` (the repaid stablecoins converted into the collateral)
+// between the liquidator and the treasury, and caps it by the close factor of `balance`.
+// An underwater position can be seized in full, the liquidator is paid first out of what is
+// left and the treasury takes the rest of its cut.
+pub fn liquidation_amounts(
+    repaid:u64,
+    balance:u64,
+    bonus_bps:u64,
+    fee_bps:u64,
+    close_factor:u64,
+    underwater:bool,
+) -> Result<LiquidationAmounts> {
+    let bonus = repaid.checked_mul(bonus_bps).ok_or(ErrorCode::MathOverflow)?.checked_div(BASIS_POINTS).ok_or(ErrorCode::DivideByZero)?;
+    let mut seized = repaid.checked_add(bonus).ok_or(ErrorCode::MathOverflow)?;
+    let mut fee = fee_amount(repaid, fee_bps)?;
+    let max_liquidation_amount = if underwater {
+        seized = seized.min(balance);
+        fee = fee.min(balance - seized);
+        balance
+    } else {
+        close_factor.checked_mul(balance).ok_or(ErrorCode::MathOverflow)?.checked_div(BASIS_POINTS).ok_or(ErrorCode::DivideByZero)?
+    };
+
+    let amounts = LiquidationAmounts { seized, fee };
+    if amounts.total()? > max_liquidation_amount {
+        return Err(ErrorCode::MaxLiquidationAmount.into())
+    }
+    Ok(amounts)
+}
 
-pub fn calculate_health_factor<'info>(
+
+// Health factor as a WAD, so 1.0 == `WAD` and 1.99 is kept apart from 1.0.
+// The collateral is weighted by the liquidation threshold (basis points) and the
+// result is rounded down, a position with no debt returns `u128::MAX`.
+pub fn calculate_health_factor(
     borrowed_amt: u64,
     collateral_amount_in_usd: u64,
-    max_lts: u64,
-) -> u64 {
+    liq_thx: u64,
+) -> Result<u128> {
+    let weighted_collateral = risk_weighted_value(collateral_amount_in_usd, liq_thx)?;
+    health_factor_from_weighted(borrowed_amt, weighted_collateral)
+}
+
+// USD value multiplied by its liquidation threshold, kept in usd * basis points
+// so the values of several assets can be summed without losing precision.
+pub fn risk_weighted_value(collateral_amount_in_usd: u64, liq_thx: u64) -> Result<u128> {
+    Ok((collateral_amount_in_usd as u128)
+        .checked_mul(liq_thx as u128).ok_or(ErrorCode::MathOverflow)?)
+}
+
+// Health factor (WAD) of `borrowed_amt` against a sum of `risk_weighted_value`s.
+pub fn health_factor_from_weighted(borrowed_amt: u64, weighted_collateral: u128) -> Result<u128> {
     if borrowed_amt == 0 {
-        return u64::MAX; // or 1, or any sentinel value you prefer
+        return Ok(u128::MAX);
     }
 
-    collateral_amount_in_usd
-        .checked_mul(max_lts).expect("overflow in collateral * max_lts")
-        .checked_div(borrowed_amt).expect("division by borrowed_amt")
-        .checked_div(10000).expect("division by 10000")
+    let scaled_debt = (borrowed_amt as u128)
+        .checked_mul(BASIS_POINTS as u128).ok_or(ErrorCode::MathOverflow)?;
+
+    mul_div(weighted_collateral, WAD, scaled_debt, Rounding::Down)
+}
+
+// Auction price `elapsed` seconds after the auction opened at `start_price`. Decays
+// linearly to `floor_bps` of the start price over `duration` seconds and stays there.
+pub fn auction_price(start_price:u128, elapsed:i64, duration:i64, floor_bps:u64) -> Result<u128> {
+    let floor_price = mul_div(start_price, floor_bps as u128, BASIS_POINTS as u128, Rounding::Up)?;
+    if elapsed <= 0 {
+        return Ok(start_price);
+    }
+    if elapsed >= duration {
+        return Ok(floor_price);
+    }
+    let decay = mul_div(start_price - floor_price, elapsed as u128, duration as u128, Rounding::Down)?;
+    Ok(start_price - decay)
+}
+
+// A liquidation of a SOL `Collateral` position, priced by `quote_sol_liquidation`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SolLiquidation {
+    // normalized debt taken off the position
+    pub repaid_coins:u64,
+    pub amounts:LiquidationAmounts,
+}
+
+// Shared by `liquidate` and `liquidate_with_pool`. Checks the position is unhealthy at
+// `price_in_usd` and splits the lamports seized for repaying `coin_amount` between the
+// liquidator (or the pool) and the treasury.
+pub fn quote_sol_liquidation(collateral:&Collateral, config:&Config, price_in_usd:u128, coin_amount:u64) -> Result<SolLiquidation> {
+    let collateral_in_usd = lamports_to_usd(collateral.lamports, price_in_usd, Rounding::Down)?;
+    let debt = config.debt_of(collateral.coins)?;
+    if calculate_health_factor(debt, collateral_in_usd, config.liq_thx)? >= WAD {
+        return Err(ErrorCode::HealthFactorError.into())
+    }
+    let repaid_coins = config.normalized_repayment(collateral.coins, coin_amount)?;
+
+    // a position worth less than its debt can be seized in full, whatever debt is left
+    // once the collateral is gone is written off by `settle_bad_debt`
+    let underwater = collateral_in_usd < debt;
+    let coin_amount_in_lamports = usd_to_lamports(coin_amount, price_in_usd, Rounding::Down)?;
+    let amounts = liquidation_amounts(coin_amount_in_lamports, collateral.lamports, config.liq_bonus, config.liquidation_fee_bps, config.close_factor, underwater)?;
+    Ok(SolLiquidation { repaid_coins, amounts })
+}
+
+// Books a quoted liquidation on the position, the protocol debt and the protocol stats,
+// returns the health factor the position is left with.
+pub fn settle_sol_liquidation(
+    collateral:&mut Collateral,
+    config:&mut Config,
+    protocol_state:&mut ProtocolState,
+    liquidation:&SolLiquidation,
+    coin_amount:u64,
+    price_in_usd:u128,
+) -> Result<u128> {
+    let total_seized = liquidation.amounts.total()?;
+    collateral.coins = collateral.coins.checked_sub(liquidation.repaid_coins).ok_or(ErrorCode::MathOverflow)?;
+    collateral.lamports = collateral.lamports.checked_sub(total_seized).ok_or(ErrorCode::MathOverflow)?;
+    config.remove_debt(liquidation.repaid_coins)?;
+
+    protocol_state.remove_collateral(total_seized)?;
+    protocol_state.record_liquidation(coin_amount)?;
+    protocol_state.record_fee(lamports_to_usd(liquidation.amounts.fee, price_in_usd, Rounding::Down)?)?;
+    protocol_state.sync_debt(config)?;
+
+    let remaining_collateral_in_usd = lamports_to_usd(collateral.lamports, price_in_usd, Rounding::Down)?;
+    calculate_health_factor(config.debt_of(collateral.coins)?, remaining_collateral_in_usd, config.liq_thx)
+}
+
+// Unlocks a position whose auction is over and returns the auction account's rent to its keeper.
+pub fn close_auction<'info>(
+    collateral:&mut Account<'info, Collateral>,
+    auction:&Account<'info, Auction>,
+    keeper:&AccountInfo<'info>,
+) -> Result<()> {
+    collateral.in_auction = false;
+    auction.close(keeper.clone())
+}
+
+// Largest debt `collateral_amount_in_usd` supports at `min_health_factor` (basis points),
+// the same bound `calculate_health_factor` enforces when minting.
+pub fn max_debt_for_collateral(collateral_amount_in_usd:u64, liq_thx:u64, min_health_factor:u64) -> Result<u64> {
+    let weighted_collateral = risk_weighted_value(collateral_amount_in_usd, liq_thx)?;
+    to_u64(mul_div(weighted_collateral, 1, min_health_factor as u128, Rounding::Down)?)
+}
+
+// Collateral value in USD a debt needs to stay at `min_health_factor`, rounded up.
+pub fn min_collateral_for_debt(borrowed_amt:u64, liq_thx:u64, min_health_factor:u64) -> Result<u64> {
+    to_u64(mul_div(borrowed_amt as u128, min_health_factor as u128, liq_thx as u128, Rounding::Up)?)
+}
+
+// WAD price of SOL at which a position with `lamports` of collateral reaches a health
+// factor of 1.0. Zero without debt, `u128::MAX` when there is debt but no collateral.
+pub fn liquidation_price(borrowed_amt:u64, lamports:u64, liq_thx:u64) -> Result<u128> {
+    if borrowed_amt == 0 {
+        return Ok(0);
+    }
+    if lamports == 0 {
+        return Ok(u128::MAX);
+    }
+    let scaled_debt = (borrowed_amt as u128)
+        .checked_mul(BASIS_POINTS as u128).ok_or(ErrorCode::MathOverflow)?;
+    let weighted_collateral = (lamports as u128)
+        .checked_mul(liq_thx as u128).ok_or(ErrorCode::MathOverflow)?;
+    mul_div(scaled_debt, WAD, weighted_collateral, Rounding::Up)
+}
+
+// Value of a cross-collateral position and the prices it was valued at.
+pub struct PositionValue {
+    // sum of `risk_weighted_value` over every asset in the position
+    pub weighted_collateral:u128,
+    // unweighted USD value of every asset, compared against the debt to spot an underwater position
+    pub collateral_in_usd:u64,
+    pub sol_price:u128,
+    // one price per entry of `Position.balances`, in the same order
+    pub token_prices:Vec<u128>,
+    // `risk_weighted_value` of each entry of `Position.balances`, in the same order
+    pub token_weighted:Vec<u128>,
+}
+
+// Values every asset of a position with its own threshold and oracle. SOL is priced
+// with `sol_price_update`, and `remaining_accounts` holds one
+// (`CollateralType`, `PriceUpdateV2`) pair per entry of `position.balances`, in order.
+pub fn value_position(
+    position:&Position,
+    config:&Config,
+    sol_price_update:&PriceUpdateV2,
+    remaining_accounts:&[AccountInfo],
+    bound:PriceBound,
+) -> Result<PositionValue> {
+    require!(
+        remaining_accounts.len() == position.balances.len() * 2,
+        ErrorCode::InvalidRemainingAccounts
+    );
+
+    let sol_price = oracle_price(sol_price_update, config, bound)?;
+    let sol_in_usd = lamports_to_usd(position.lamports, sol_price, Rounding::Down)?;
+    let mut weighted_collateral = risk_weighted_value(sol_in_usd, config.liq_thx)?;
+    let mut collateral_in_usd = sol_in_usd;
+    let mut token_prices = Vec::with_capacity(position.balances.len());
+    let mut token_weighted = Vec::with_capacity(position.balances.len());
+
+    for (balance, accounts) in position.balances.iter().zip(remaining_accounts.chunks(2)) {
+        require_keys_eq!(accounts[0].key(), balance.collateral_type, ErrorCode::InvalidRemainingAccounts);
+        let collateral_type = load_account::<CollateralType>(&accounts[0])?;
+        let price_update = load_account::<PriceUpdateV2>(&accounts[1])?;
+
+        let price = feed_price(&price_update, config, &collateral_type.feed_id, bound)?;
+        let value_in_usd = token_to_usd(balance.amount, collateral_type.decimals, price, Rounding::Down)?;
+        let weighted = risk_weighted_value(value_in_usd, collateral_type.liq_thx)?;
+        weighted_collateral = weighted_collateral.checked_add(weighted).ok_or(ErrorCode::MathOverflow)?;
+        collateral_in_usd = collateral_in_usd.checked_add(value_in_usd).ok_or(ErrorCode::MathOverflow)?;
+        token_prices.push(price);
+        token_weighted.push(weighted);
+    }
+
+    Ok(PositionValue { weighted_collateral, collateral_in_usd, sol_price, token_prices, token_weighted })
+}
+
+// Splits `coins` of normalized debt across the SPL balances of a position pro-rata to the weighted
+// value each one adds, one share per entry of `Position.balances`. The rest is backed by SOL,
+// which has no debt ceiling.
+pub fn position_debt_shares(coins:u64, value:&PositionValue) -> Result<Vec<u64>> {
+    value.token_weighted.iter().map(|weighted| {
+        if value.weighted_collateral == 0 {
+            return Ok(0);
+        }
+        let share = mul_div(coins as u128, *weighted, value.weighted_collateral, Rounding::Up)?;
+        Ok(share.min(coins as u128) as u64)
+    }).collect()
+}
+
+// Shares of a position's SPL balances once `repaid_coins` of its debt are paid off, each one
+// reduced pro-rata to the repayment.
+pub fn repaid_position_debt_shares(position:&Position, repaid_coins:u64) -> Result<Vec<u64>> {
+    position.balances.iter().map(|balance| {
+        if position.coins == 0 {
+            return Ok(balance.debt);
+        }
+        let released = mul_div(balance.debt as u128, repaid_coins as u128, position.coins as u128, Rounding::Up)?;
+        Ok(balance.debt - released.min(balance.debt as u128) as u64)
+    }).collect()
+}
+
+// Moves the debt each SPL balance of a position counts against its collateral type to `shares`,
+// checking the debt ceiling of every type whose share grows. `collateral_types` holds the
+// `CollateralType` of every entry of `position.balances`, in order.
+pub fn assign_position_debt<'a, 'info: 'a>(
+    position:&mut Position,
+    config:&Config,
+    collateral_types:impl ExactSizeIterator<Item = &'a AccountInfo<'info>>,
+    shares:&[u64],
+) -> Result<()> {
+    require!(collateral_types.len() == position.balances.len(), ErrorCode::InvalidRemainingAccounts);
+
+    for ((balance, info), share) in position.balances.iter_mut().zip(collateral_types).zip(shares) {
+        if balance.debt == *share {
+            continue;
+        }
+        require_keys_eq!(info.key(), balance.collateral_type, ErrorCode::InvalidRemainingAccounts);
+        let mut collateral_type = load_account::<CollateralType>(info)?;
+        collateral_type.total_debt = collateral_type.total_debt
+            .checked_sub(balance.debt).ok_or(ErrorCode::MathOverflow)?
+            .checked_add(*share).ok_or(ErrorCode::MathOverflow)?;
+        if *share > balance.debt {
+            require!(config.debt_of(collateral_type.total_debt)? <= collateral_type.debt_ceiling, ErrorCode::CollateralDebtCeilingExceeded);
+        }
+        store_account(info, &collateral_type)?;
+        balance.debt = *share;
+    }
+    Ok(())
+}
+
+// Deserializes an account passed through `remaining_accounts` after checking its owner.
+pub fn load_account<T: AccountDeserialize + Owner>(info:&AccountInfo) -> Result<T> {
+    require_keys_eq!(*info.owner, T::owner(), ErrorCode::InvalidRemainingAccounts);
+    let data = info.try_borrow_data()?;
+    T::try_deserialize(&mut &data[..])
+}
+
+
+// Writes an account loaded with `load_account` back into its data.
+pub fn store_account<T: AccountSerialize>(info:&AccountInfo, account:&T) -> Result<()> {
+    require!(info.is_writable, ErrorCode::InvalidRemainingAccounts);
+    let mut data = info.try_borrow_mut_data()?;
+    account.try_serialize(&mut &mut data[..])
+}
+
+// Moves lamports out of a depositer's SOL vault PDA, signing with the vault seeds.
+pub fn transfer_from_vault<'info>(
+    system_program:&Program<'info, System>,
+    vault:&AccountInfo<'info>,
+    to:&AccountInfo<'info>,
+    depositer:&Pubkey,
+    vault_bump:u8,
+    lamports:u64,
+) -> Result<()> {
+    transfer_signed(system_program, vault, to, &[COLLATERAL_VAULT_SEED, depositer.as_ref(), &[vault_bump]], lamports)
+}
+
+// Moves lamports out of a cross-collateral position's SOL vault PDA.
+pub fn transfer_from_position_vault<'info>(
+    system_program:&Program<'info, System>,
+    vault:&AccountInfo<'info>,
+    to:&AccountInfo<'info>,
+    owner:&Pubkey,
+    vault_bump:u8,
+    lamports:u64,
+) -> Result<()> {
+    transfer_signed(system_program, vault, to, &[POSITION_VAULT_SEED, owner.as_ref(), &[vault_bump]], lamports)
+}
+
+fn transfer_signed<'info>(
+    system_program:&Program<'info, System>,
+    vault:&AccountInfo<'info>,
+    to:&AccountInfo<'info>,
+    vault_seeds:&[&[u8]],
+    lamports:u64,
+) -> Result<()> {
+    let signer_seeds:&[&[&[u8]]] = &[vault_seeds];
+
+    let ctx = CpiContext::new_with_signer(
+        system_program.to_account_info(),
+        Transfer {
+            from:vault.to_account_info(),
+            to:to.to_account_info(),
+        },
+        signer_seeds);
+
+    transfer(ctx, lamports)
+}
+
+
+// Moves collateral tokens out of a collateral type's vault, signing as the collateral type PDA.
+pub fn transfer_from_collateral_vault<'info>(
+    token_program:&Interface<'info, TokenInterface>,
+    vault:&InterfaceAccount<'info, TokenAccount>,
+    to:&InterfaceAccount<'info, TokenAccount>,
+    mint:&InterfaceAccount<'info, Mint>,
+    collateral_type:&Account<'info, CollateralType>,
+    amount:u64,
+) -> Result<()> {
+    let signer_seeds:&[&[&[u8]]] = &[&[COLLATERAL_TYPE_SEED, collateral_type.mint.as_ref(), &[collateral_type.bump]]];
+
+    let ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        TransferChecked {
+            from:vault.to_account_info(),
+            mint:mint.to_account_info(),
+            to:to.to_account_info(),
+            authority:collateral_type.to_account_info(),
+        },
+        signer_seeds);
+
+    transfer_checked(ctx, amount, collateral_type.decimals)
 }
 
 
@@ -80,8 +602,7 @@ pub fn burn_tokens<'info>(
     user:&InterfaceAccount<'info, TokenAccount>,
     amount:u64,
     withdrawer:&Signer<'info>
-){
-    let amount_in_lamports = amount.checked_mul(1000000000).unwrap();
+) -> Result<()> {
     let ctx = CpiContext::new(
         token_program.to_account_info(),
          Burn{
@@ -90,6 +611,202 @@ pub fn burn_tokens<'info>(
             from:user.to_account_info(),
          }
     );
-    burn(ctx, amount_in_lamports);
+    burn(ctx, amount)
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{tests::test_config, PositionBalance};
+
+    #[test]
+    fn min_health_factor_is_compared_in_wad() {
+        let config = test_config();
+        assert_eq!(config.min_health_factor_wad().unwrap(), 5 * WAD / 4);
+
+        // 1.25 passes, one base unit of extra debt does not
+        let at_minimum = calculate_health_factor(80_000_000_000, 125_000_000_000, config.liq_thx).unwrap();
+        let below_minimum = calculate_health_factor(80_000_000_001, 125_000_000_000, config.liq_thx).unwrap();
+        assert!(at_minimum >= config.min_health_factor_wad().unwrap());
+        assert!(below_minimum < config.min_health_factor_wad().unwrap());
+    }
+
+    #[test]
+    fn risk_params_reject_min_health_factor_below_one() {
+        assert!(validate_risk_params(8000, 500, BASIS_POINTS, 5000).is_ok());
+        assert!(validate_risk_params(8000, 500, BASIS_POINTS - 1, 5000).is_err());
+        assert!(validate_risk_params(8000, 500, 1, 5000).is_err());
+    }
+
+    #[test]
+    fn position_limits_match_the_health_factor_check() {
+        let config = test_config();
+        let min_health_factor_wad = config.min_health_factor_wad().unwrap();
+
+        // $125 at an 80% threshold and a 1.25 minimum supports $80
+        let max_debt = max_debt_for_collateral(125_000_000_000, config.liq_thx, config.min_health_factor).unwrap();
+        assert_eq!(max_debt, 80_000_000_000);
+        assert!(calculate_health_factor(max_debt, 125_000_000_000, config.liq_thx).unwrap() >= min_health_factor_wad);
+        assert!(calculate_health_factor(max_debt + 1, 125_000_000_000, config.liq_thx).unwrap() < min_health_factor_wad);
+
+        let min_collateral = min_collateral_for_debt(80_000_000_001, config.liq_thx, config.min_health_factor).unwrap();
+        assert!(calculate_health_factor(80_000_000_001, min_collateral, config.liq_thx).unwrap() >= min_health_factor_wad);
+        assert!(calculate_health_factor(80_000_000_001, min_collateral - 1, config.liq_thx).unwrap() < min_health_factor_wad);
+    }
+
+    #[test]
+    fn max_mintable_survives_the_mint_rounding() {
+        let mut config = test_config();
+        config.debt_index = WAD + WAD / 3;
+        config.total_debt = 30_000_000_000;
+        let coins = 10_000_000_000;
+        let collateral_in_usd = 125_000_000_000;
+        let max_debt = max_debt_for_collateral(collateral_in_usd, config.liq_thx, config.min_health_factor).unwrap();
+        let max_mintable = config.max_mintable(coins, max_debt).unwrap();
+
+        // the same checks `mint_stable` runs on the new debt
+        fn mint(config:&Config, coins:u64, collateral_in_usd:u64, amount:u64) -> Result<u128> {
+            let mut config = config.clone();
+            let normalized_amount = config.normalize(amount, Rounding::Up)?;
+            let new_coins_balance = coins + normalized_amount;
+            config.add_debt(normalized_amount, new_coins_balance)?;
+            calculate_health_factor(config.debt_of(new_coins_balance)?, collateral_in_usd, config.liq_thx)
+        }
+        assert!(mint(&config, coins, collateral_in_usd, max_mintable).unwrap() >= config.min_health_factor_wad().unwrap());
+        assert!(mint(&config, coins, collateral_in_usd, max_mintable + 2).unwrap() < config.min_health_factor_wad().unwrap());
+
+        // the global ceiling is checked the same way
+        config.global_debt_ceiling = config.debt_of(config.total_debt).unwrap() + 1_000;
+        let max_mintable = config.max_mintable(coins, max_debt).unwrap();
+        assert!(mint(&config, coins, collateral_in_usd, max_mintable).is_ok());
+        assert!(mint(&config, coins, collateral_in_usd, max_mintable + 2).is_err());
+    }
 
-}
\ No newline at end of file
+    #[test]
+    fn every_liquidation_pays_the_treasury_fee() {
+        let config = test_config();
+        let fee_bps = 200;
+
+        // SOL paths (`liquidate`, `liquidate_with_pool`, `liquidate_position_sol`) use the config bonus
+        let sol = liquidation_amounts(1_000_000_000, 10_000_000_000, config.liq_bonus, fee_bps, config.close_factor, false).unwrap();
+        assert_eq!(sol, LiquidationAmounts { seized: 1_050_000_000, fee: 20_000_000 });
+        assert_eq!(sol.total().unwrap(), 1_070_000_000);
+
+        // token paths (`liquidate_token_collateral`, `liquidate_position_token`) use the collateral type bonus
+        let token = liquidation_amounts(1_000_000, 10_000_000, 1000, fee_bps, config.close_factor, false).unwrap();
+        assert_eq!(token, LiquidationAmounts { seized: 1_100_000, fee: 20_000 });
+
+        // the fee counts against the close factor
+        assert!(liquidation_amounts(4_700_000, 10_000_000, config.liq_bonus, 0, config.close_factor, false).is_ok());
+        assert!(liquidation_amounts(4_700_000, 10_000_000, config.liq_bonus, fee_bps, config.close_factor, false).is_err());
+
+        // `bid_auction` charges the same fee on the lot
+        assert_eq!(fee_amount(2_000_000_000, fee_bps).unwrap(), 40_000_000);
+    }
+
+    #[test]
+    fn sol_liquidation_quotes_and_books_the_seized_collateral() {
+        let mut config = test_config();
+        config.liquidation_fee_bps = 200;
+        config.total_debt = 90_000_000_000;
+        let mut protocol_state = ProtocolState {
+            total_collateral_lamports: 1_000_000_000,
+            total_debt: 0,
+            position_count: 1,
+            total_liquidated: 0,
+            cumulative_fees: 0,
+            bad_debt: 0,
+            bump: 0,
+        };
+        // 1 SOL at $100 against $90 of debt at an 80% threshold
+        let mut collateral = Collateral {
+            depositer: Pubkey::default(),
+            sol_account: Pubkey::default(),
+            coin_token_account: Pubkey::default(),
+            is_initialized: true,
+            lamports: 1_000_000_000,
+            coins: 90_000_000_000,
+            in_auction: false,
+            bump: 0,
+            bump_sol_account: 0,
+        };
+        let price = 100 * WAD;
+        assert!(quote_sol_liquidation(&collateral, &config, 120 * WAD, 10_000_000_000).is_err());
+
+        let liquidation = quote_sol_liquidation(&collateral, &config, price, 10_000_000_000).unwrap();
+        assert_eq!(liquidation.repaid_coins, 10_000_000_000);
+        assert_eq!(liquidation.amounts, LiquidationAmounts { seized: 105_000_000, fee: 2_000_000 });
+
+        settle_sol_liquidation(&mut collateral, &mut config, &mut protocol_state, &liquidation, 10_000_000_000, price).unwrap();
+        assert_eq!(collateral.coins, 80_000_000_000);
+        assert_eq!(collateral.lamports, 893_000_000);
+        assert_eq!(config.total_debt, 80_000_000_000);
+        assert_eq!(protocol_state.total_collateral_lamports, 893_000_000);
+        assert_eq!(protocol_state.total_liquidated, 10_000_000_000);
+        assert_eq!(protocol_state.cumulative_fees, 200_000_000);
+        assert_eq!(protocol_state.total_debt, 80_000_000_000);
+    }
+
+    #[test]
+    fn underwater_liquidation_pays_the_liquidator_before_the_treasury() {
+        let config = test_config();
+        let amounts = liquidation_amounts(1_000, 1_080, config.liq_bonus, 500, config.close_factor, true).unwrap();
+        assert_eq!(amounts, LiquidationAmounts { seized: 1_050, fee: 30 });
+
+        let amounts = liquidation_amounts(1_000, 1_020, config.liq_bonus, 500, config.close_factor, true).unwrap();
+        assert_eq!(amounts, LiquidationAmounts { seized: 1_020, fee: 0 });
+    }
+
+    #[test]
+    fn mint_fee_is_split_from_the_minted_debt() {
+        let mut config = test_config();
+        config.mint_fee_bps = 50;
+        let amount = 1_000_000_000;
+        let fee = fee_amount(amount, config.mint_fee_bps).unwrap();
+        assert_eq!(fee, 5_000_000);
+        assert_eq!(fee_amount(199, config.mint_fee_bps).unwrap(), 0);
+    }
+
+    #[test]
+    fn position_debt_is_split_by_weighted_value_and_released_on_repayment() {
+        let value = PositionValue {
+            weighted_collateral: 4_000,
+            collateral_in_usd: 5_000,
+            sol_price: WAD,
+            token_prices: vec![WAD, WAD],
+            token_weighted: vec![1_000, 2_000],
+        };
+        // SOL backs the remaining quarter, the shares round up against the ceilings
+        let shares = position_debt_shares(1_001, &value).unwrap();
+        assert_eq!(shares, vec![251, 501]);
+
+        let balance = |debt| PositionBalance { collateral_type: Pubkey::new_unique(), amount: 1, debt };
+        let mut position = Position {
+            owner: Pubkey::default(),
+            coins: 1_001,
+            lamports: 0,
+            balances: vec![balance(251), balance(501)],
+            bump: 0,
+            bump_sol_account: 0,
+        };
+        assert_eq!(repaid_position_debt_shares(&position, 500).unwrap(), vec![125, 250]);
+        assert_eq!(repaid_position_debt_shares(&position, 1_001).unwrap(), vec![0, 0]);
+
+        position.coins = 0;
+        position.balances = vec![balance(0)];
+        assert_eq!(repaid_position_debt_shares(&position, 0).unwrap(), vec![0]);
+    }
+
+    #[test]
+    fn liquidation_bonus_is_read_in_basis_points() {
+        // the bonus is validated as basis points, 5% here and 26% breaking the threshold
+        let config = test_config();
+        validate_liquidation_params(config.liq_thx, config.liq_bonus).unwrap();
+        assert!(validate_liquidation_params(config.liq_thx, 2_600).is_err());
+
+        // so a liquidator repaying 10_000 gets 5% on top, not the 0.5% a 100_000 divisor paid
+        let amounts = liquidation_amounts(10_000, 100_000, config.liq_bonus, 0, config.close_factor, false).unwrap();
+        assert_eq!(amounts.seized, 10_500);
+    }
+}
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+use crate::{calculate_health_factor, error::ErrorCode, events::AuctionStarted, lamports_to_usd, math::{mul_div, Rounding, WAD}, oracle_price, state::{Auction, Collateral, Config, ProtocolState}, PriceBound, BASIS_POINTS, PAUSE_LIQUIDATE};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct StartAuction<'info>{
+    #[account(mut)]
+    pub keeper:Signer<'info>,
+    #[account(
+        mut,
+        seeds=[b"collateral", collateral_account.depositer.as_ref()],
+        bump=collateral_account.bump
+    )]
+    pub collateral_account:Account<'info,Collateral>,
+    #[account(
+        init_if_needed,
+        payer=keeper,
+        seeds=[b"auction", collateral_account.key().as_ref()],
+        space= 8 + Auction::INIT_SPACE,
+        bump
+    )]
+    pub auction:Account<'info,Auction>,
+    #[account(
+        mut,
+        seeds=[b"config"],
+        bump=config.bump
+    )]
+    pub config:Account<'info,Config>,
+    #[account(
+        mut,
+        seeds=[b"protocol_state"],
+        bump=protocol_state.bump
+    )]
+    pub protocol_state:Account<'info,ProtocolState>,
+    pub price_update:Account<'info,PriceUpdateV2>,
+    pub system_program:Program<'info,System>,
+}
+
+//  1. The position must be unhealthy, a running auction can only be restarted once its
+//     price has decayed to the floor without covering the debt. A position that is healthy
+//     again leaves its auction through `repay`
+//  2. Open the auction above the oracle price so the price discovers downwards
+//  3. Lock the position until the auction settles
+
+pub fn process_start_auction(ctx:Context<StartAuction>) -> Result<()> {
+    require!(!ctx.accounts.config.is_paused(PAUSE_LIQUIDATE), ErrorCode::ProtocolPaused);
+
+    let collateral = &mut ctx.accounts.collateral_account;
+    let config = &mut ctx.accounts.config;
+    config.accrue_interest()?;
+    let now = Clock::get()?.unix_timestamp;
+
+    // liquidations are judged at the high end of the confidence interval
+    let price_in_usd = oracle_price(&ctx.accounts.price_update, config, PriceBound::Upper)?;
+    let debt = config.debt_of(collateral.coins)?;
+
+    // 1. only unhealthy positions are auctioned, restarts included
+    let collateral_in_usd = lamports_to_usd(collateral.lamports, price_in_usd, Rounding::Down)?;
+    let health_factor = calculate_health_factor(debt, collateral_in_usd, config.liq_thx)?;
+    require!(health_factor < WAD, ErrorCode::HealthFactorError);
+    if collateral.in_auction {
+        let elapsed = now.saturating_sub(ctx.accounts.auction.started_at);
+        require!(elapsed >= config.auction_duration, ErrorCode::AuctionActive);
+    }
+
+    // 2. open above the oracle price, a restart keeps the keeper that paid the rent
+    let keeper = if collateral.in_auction { ctx.accounts.auction.keeper } else { ctx.accounts.keeper.key() };
+    let start_price = mul_div(price_in_usd, config.auction_buffer_bps as u128, BASIS_POINTS as u128, Rounding::Up)?;
+    ctx.accounts.auction.set_inner(Auction {
+        position: collateral.key(),
+        owner: collateral.depositer,
+        keeper,
+        start_price,
+        started_at: now,
+        bump: ctx.bumps.auction,
+    });
+
+    // 3. lock the position
+    collateral.in_auction = true;
+    ctx.accounts.protocol_state.sync_debt(config)?;
+
+    emit_cpi!(AuctionStarted {
+        keeper: ctx.accounts.keeper.key(),
+        position: ctx.accounts.collateral_account.key(),
+        auction: ctx.accounts.auction.key(),
+        debt,
+        lamports: ctx.accounts.collateral_account.lamports,
+        start_price,
+    });
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked}};
+
+use crate::{error::ErrorCode, events::PsmSwapped, fee_amount, math::{rescale, to_u64, Rounding}, mint_tokens, state::{Config, ProtocolState, Psm}, PAUSE_PSM, STABLE_DECIMALS};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SwapIn<'info> {
+    #[account(mut)]
+    pub user:Signer<'info>,
+    #[account(
+        mut,
+        token::mint=reserve_mint,
+        token::authority=user,
+        token::token_program=reserve_token_program
+    )]
+    pub user_reserve_account:InterfaceAccount<'info,TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer=user,
+        associated_token::mint=mint,
+        associated_token::authority=user,
+        associated_token::token_program=token_program_2022
+    )]
+    pub user_token_account:InterfaceAccount<'info,TokenAccount>,
+    #[account(
+        mut,
+        seeds=[b"config"],
+        bump=config.bump
+    )]
+    pub config:Account<'info,Config>,
+    #[account(
+        mut,
+        seeds=[b"protocol_state"],
+        bump=protocol_state.bump
+    )]
+    pub protocol_state:Account<'info,ProtocolState>,
+    #[account(
+        seeds=[b"psm"],
+        bump=psm.bump,
+        has_one=reserve_mint,
+        has_one=vault
+    )]
+    pub psm:Account<'info,Psm>,
+    #[account(mut)]
+    pub vault:InterfaceAccount<'info,TokenAccount>,
+    pub reserve_mint:InterfaceAccount<'info,Mint>,
+    #[account(
+        mut,
+        seeds=[b"jacked_nerd"],
+        mint::authority=mint,
+        mint::freeze_authority=mint,
+        mint::token_program=token_program_2022,
+        bump
+    )]
+    pub mint:InterfaceAccount<'info,Mint>,
+    #[account(
+        mut,
+        seeds=[b"treasury_token_account"],
+        bump
+    )]
+    pub treasury_token_account:InterfaceAccount<'info,TokenAccount>,
+    pub reserve_token_program:Interface<'info,TokenInterface>,
+    pub token_program_2022:Interface<'info,TokenInterface>,
+    pub associated_token_program:Program<'info,AssociatedToken>,
+    pub system_program:Program<'info,System>,
+}
+
+//  1. Value the reserve tokens 1:1 in stablecoins and book them against the PSM ceiling
+//  2. Move the reserve tokens into the PSM vault
+//  3. Mint the stablecoins to the user, less the fee which is minted to the treasury
+
+pub fn process_swap_in(ctx:Context<SwapIn>, amount:u64) -> Result<()> {
+    require!(!ctx.accounts.config.is_paused(PAUSE_PSM), ErrorCode::ProtocolPaused);
+
+    // 1. one reserve token is worth one stablecoin
+    let coins = to_u64(rescale(amount as u128, ctx.accounts.psm.decimals, STABLE_DECIMALS, Rounding::Down)?)?;
+    require!(coins > 0, ErrorCode::InvalidAmount);
+    let config = &mut ctx.accounts.config;
+    config.add_psm_debt(coins)?;
+    let fee = fee_amount(coins, config.psm_fee_in_bps)?;
+
+    // 2. take the reserve tokens
+    let context = CpiContext::new(
+        ctx.accounts.reserve_token_program.to_account_info(),
+        TransferChecked {
+            from:ctx.accounts.user_reserve_account.to_account_info(),
+            mint:ctx.accounts.reserve_mint.to_account_info(),
+            to:ctx.accounts.vault.to_account_info(),
+            authority:ctx.accounts.user.to_account_info(),
+        });
+    transfer_checked(context, amount, ctx.accounts.reserve_mint.decimals)?;
+
+    // 3. mint the stablecoins
+    mint_tokens(
+        &ctx.accounts.user_token_account,
+        &ctx.accounts.token_program_2022,
+        &ctx.accounts.mint,
+        config.bump_mint_acc,
+        coins - fee
+    )?;
+    if fee > 0 {
+        mint_tokens(
+            &ctx.accounts.treasury_token_account,
+            &ctx.accounts.token_program_2022,
+            &ctx.accounts.mint,
+            config.bump_mint_acc,
+            fee
+        )?;
+    }
+    ctx.accounts.protocol_state.record_fee(fee)?;
+
+    emit_cpi!(PsmSwapped {
+        user: ctx.accounts.user.key(),
+        swap_in: true,
+        reserve: amount,
+        coins,
+        fee,
+    });
+    Ok(())
+}
@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked}};
+
+use crate::{burn_tokens, error::ErrorCode, events::PsmSwapped, fee_amount, math::{rescale, to_u64, Rounding}, state::{Config, ProtocolState, Psm}, PAUSE_PSM, PSM_SEED, STABLE_DECIMALS};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SwapOut<'info> {
+    #[account(mut)]
+    pub user:Signer<'info>,
+    #[account(
+        mut,
+        token::mint=reserve_mint,
+        token::authority=user,
+        token::token_program=reserve_token_program
+    )]
+    pub user_reserve_account:InterfaceAccount<'info,TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint=mint,
+        associated_token::authority=user,
+        associated_token::token_program=token_program_2022
+    )]
+    pub user_token_account:InterfaceAccount<'info,TokenAccount>,
+    #[account(
+        mut,
+        seeds=[b"config"],
+        bump=config.bump
+    )]
+    pub config:Account<'info,Config>,
+    #[account(
+        mut,
+        seeds=[b"protocol_state"],
+        bump=protocol_state.bump
+    )]
+    pub protocol_state:Account<'info,ProtocolState>,
+    #[account(
+        seeds=[b"psm"],
+        bump=psm.bump,
+        has_one=reserve_mint,
+        has_one=vault
+    )]
+    pub psm:Account<'info,Psm>,
+    #[account(mut)]
+    pub vault:InterfaceAccount<'info,TokenAccount>,
+    pub reserve_mint:InterfaceAccount<'info,Mint>,
+    #[account(
+        mut,
+        seeds=[b"jacked_nerd"],
+        mint::authority=mint,
+        mint::freeze_authority=mint,
+        mint::token_program=token_program_2022,
+        bump
+    )]
+    pub mint:InterfaceAccount<'info,Mint>,
+    #[account(
+        mut,
+        seeds=[b"treasury_token_account"],
+        bump
+    )]
+    pub treasury_token_account:InterfaceAccount<'info,TokenAccount>,
+    pub reserve_token_program:Interface<'info,TokenInterface>,
+    pub token_program_2022:Interface<'info,TokenInterface>,
+    pub associated_token_program:Program<'info,AssociatedToken>,
+}
+
+//  1. Take the fee out of the stablecoins and value the rest 1:1 in reserve tokens
+//  2. Burn the stablecoins the reserve pays for and send the fee to the treasury
+//  3. Pay the reserve tokens out of the PSM vault
+
+pub fn process_swap_out(ctx:Context<SwapOut>, amount:u64) -> Result<()> {
+    require!(!ctx.accounts.config.is_paused(PAUSE_PSM), ErrorCode::ProtocolPaused);
+
+    // 1. one stablecoin is worth one reserve token
+    let config = &mut ctx.accounts.config;
+    let fee = fee_amount(amount, config.psm_fee_out_bps)?;
+    let coins = amount - fee;
+    let reserve = to_u64(rescale(coins as u128, STABLE_DECIMALS, ctx.accounts.psm.decimals, Rounding::Down)?)?;
+    require!(reserve > 0, ErrorCode::InvalidAmount);
+    require!(reserve <= ctx.accounts.vault.amount, ErrorCode::InsufficientPsmReserve);
+    config.remove_psm_debt(coins)?;
+
+    // 2. burn what the reserve pays for, the fee goes to the treasury
+    burn_tokens(
+        &ctx.accounts.mint,
+        &ctx.accounts.token_program_2022,
+        &ctx.accounts.user_token_account,
+        coins,
+        &ctx.accounts.user
+    )?;
+    if fee > 0 {
+        let context = CpiContext::new(
+            ctx.accounts.token_program_2022.to_account_info(),
+            TransferChecked {
+                from:ctx.accounts.user_token_account.to_account_info(),
+                mint:ctx.accounts.mint.to_account_info(),
+                to:ctx.accounts.treasury_token_account.to_account_info(),
+                authority:ctx.accounts.user.to_account_info(),
+            });
+        transfer_checked(context, fee, ctx.accounts.mint.decimals)?;
+    }
+    ctx.accounts.protocol_state.record_fee(fee)?;
+
+    // 3. the PSM PDA signs for its vault
+    let signer_seeds:&[&[&[u8]]] = &[&[PSM_SEED, &[ctx.accounts.psm.bump]]];
+    let context = CpiContext::new_with_signer(
+        ctx.accounts.reserve_token_program.to_account_info(),
+        TransferChecked {
+            from:ctx.accounts.vault.to_account_info(),
+            mint:ctx.accounts.reserve_mint.to_account_info(),
+            to:ctx.accounts.user_reserve_account.to_account_info(),
+            authority:ctx.accounts.psm.to_account_info(),
+        },
+        signer_seeds);
+    transfer_checked(context, reserve, ctx.accounts.reserve_mint.decimals)?;
+
+    emit_cpi!(PsmSwapped {
+        user: ctx.accounts.user.key(),
+        swap_in: false,
+        reserve,
+        coins,
+        fee,
+    });
+    Ok(())
+}
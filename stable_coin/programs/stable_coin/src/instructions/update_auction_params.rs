@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, events::AuctionParamsUpdated, state::Config, BASIS_POINTS, MAX_AUCTION_BUFFER_BPS};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateAuctionParams<'info> {
+    pub authority:Signer<'info>,
+    #[account(
+        mut,
+        seeds=[b"config"],
+        bump=config.bump,
+        has_one=authority @ ErrorCode::Unauthorized
+    )]
+    pub config:Account<'info,Config>,
+}
+
+// Sets how collateral auctions are priced: the start price as basis points of the oracle
+// price, the seconds it takes to decay and the floor as basis points of the start price.
+// Running auctions pick the new decay up on their next bid.
+pub fn process_update_auction_params(
+    ctx:Context<UpdateAuctionParams>,
+    auction_buffer_bps:u64,
+    auction_duration:i64,
+    auction_floor_bps:u64,
+) -> Result<()> {
+    require!(
+        (BASIS_POINTS..=MAX_AUCTION_BUFFER_BPS).contains(&auction_buffer_bps)
+            && auction_duration > 0
+            && auction_floor_bps > 0
+            && auction_floor_bps <= BASIS_POINTS,
+        ErrorCode::InvalidAuctionParams
+    );
+
+    let config = &mut ctx.accounts.config;
+    let event = AuctionParamsUpdated {
+        authority: ctx.accounts.authority.key(),
+        old_auction_buffer_bps: config.auction_buffer_bps,
+        new_auction_buffer_bps: auction_buffer_bps,
+        old_auction_duration: config.auction_duration,
+        new_auction_duration: auction_duration,
+        old_auction_floor_bps: config.auction_floor_bps,
+        new_auction_floor_bps: auction_floor_bps,
+    };
+
+    config.auction_buffer_bps = auction_buffer_bps;
+    config.auction_duration = auction_duration;
+    config.auction_floor_bps = auction_floor_bps;
+
+    emit_cpi!(event);
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, events::ConfigUpdated, state::Config, validate_risk_params};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub authority:Signer<'info>,
+    #[account(
+        mut,
+        seeds=[b"config"],
+        bump=config.bump,
+        has_one=authority @ ErrorCode::Unauthorized
+    )]
+    pub config:Account<'info,Config>,
+}
+
+// 1. Validate the new risk parameters against each other
+// 2. Overwrite the values stored in the config account
+// 3. Emit the old and new values so off-chain services can follow the change
+
+pub fn process_update_config(
+    ctx:Context<UpdateConfig>,
+    liq_thx:u64,
+    liq_bonus:u64,
+    min_health_factor:u64,
+    close_factor:u64,
+) -> Result<()> {
+    // 1. validate the new risk parameters
+    validate_risk_params(liq_thx, liq_bonus, min_health_factor, close_factor)?;
+
+    let config = &mut ctx.accounts.config;
+    let event = ConfigUpdated {
+        authority: ctx.accounts.authority.key(),
+        old_liq_thx: config.liq_thx,
+        new_liq_thx: liq_thx,
+        old_liq_bonus: config.liq_bonus,
+        new_liq_bonus: liq_bonus,
+        old_min_health_factor: config.min_health_factor,
+        new_min_health_factor: min_health_factor,
+        old_close_factor: config.close_factor,
+        new_close_factor: close_factor,
+    };
+
+    // 2. update the config account
+    config.liq_thx = liq_thx;
+    config.liq_bonus = liq_bonus;
+    config.min_health_factor = min_health_factor;
+    config.close_factor = close_factor;
+
+    // 3. emit the change
+    emit_cpi!(event);
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, events::DebtCeilingsUpdated, state::Config};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateDebtCeilings<'info> {
+    pub authority:Signer<'info>,
+    #[account(
+        mut,
+        seeds=[b"config"],
+        bump=config.bump,
+        has_one=authority @ ErrorCode::Unauthorized
+    )]
+    pub config:Account<'info,Config>,
+}
+
+// Caps the stablecoins owed across the protocol and by any single position. Lowering a
+// ceiling below the current debt only blocks new mints, existing positions are untouched.
+pub fn process_update_debt_ceilings(
+    ctx:Context<UpdateDebtCeilings>,
+    global_debt_ceiling:u64,
+    max_debt_per_position:u64,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let event = DebtCeilingsUpdated {
+        authority: ctx.accounts.authority.key(),
+        old_global_debt_ceiling: config.global_debt_ceiling,
+        new_global_debt_ceiling: global_debt_ceiling,
+        old_max_debt_per_position: config.max_debt_per_position,
+        new_max_debt_per_position: max_debt_per_position,
+    };
+
+    config.global_debt_ceiling = global_debt_ceiling;
+    config.max_debt_per_position = max_debt_per_position;
+
+    emit_cpi!(event);
+    Ok(())
+}
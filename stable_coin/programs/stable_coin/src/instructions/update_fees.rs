@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, events::FeesUpdated, state::Config, MAX_FEE_BPS};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateFees<'info> {
+    pub authority:Signer<'info>,
+    #[account(
+        mut,
+        seeds=[b"config"],
+        bump=config.bump,
+        has_one=authority @ ErrorCode::Unauthorized
+    )]
+    pub config:Account<'info,Config>,
+}
+
+// Sets the mint, redemption and liquidation fees paid to the treasury, in basis points.
+pub fn process_update_fees(
+    ctx:Context<UpdateFees>,
+    mint_fee_bps:u64,
+    redemption_fee_bps:u64,
+    liquidation_fee_bps:u64,
+) -> Result<()> {
+    require!(
+        mint_fee_bps <= MAX_FEE_BPS && redemption_fee_bps <= MAX_FEE_BPS && liquidation_fee_bps <= MAX_FEE_BPS,
+        ErrorCode::InvalidFee
+    );
+
+    let config = &mut ctx.accounts.config;
+    let event = FeesUpdated {
+        authority: ctx.accounts.authority.key(),
+        old_mint_fee_bps: config.mint_fee_bps,
+        new_mint_fee_bps: mint_fee_bps,
+        old_redemption_fee_bps: config.redemption_fee_bps,
+        new_redemption_fee_bps: redemption_fee_bps,
+        old_liquidation_fee_bps: config.liquidation_fee_bps,
+        new_liquidation_fee_bps: liquidation_fee_bps,
+    };
+
+    config.mint_fee_bps = mint_fee_bps;
+    config.redemption_fee_bps = redemption_fee_bps;
+    config.liquidation_fee_bps = liquidation_fee_bps;
+
+    emit_cpi!(event);
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, events::OracleUpdated, state::{Config, PriceMode}, BASIS_POINTS};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateOracle<'info> {
+    pub authority:Signer<'info>,
+    #[account(
+        mut,
+        seeds=[b"config"],
+        bump=config.bump,
+        has_one=authority @ ErrorCode::Unauthorized
+    )]
+    pub config:Account<'info,Config>,
+}
+
+// Points the protocol at a different pyth feed, e.g. when the same program
+// is deployed to devnet, mainnet or a local validator.
+pub fn process_update_oracle(
+    ctx:Context<UpdateOracle>,
+    feed_id:[u8;32],
+    max_price_age:u64,
+    max_conf_bps:u64,
+    price_mode:PriceMode,
+) -> Result<()> {
+    require!(max_price_age > 0, ErrorCode::InvalidMaxPriceAge);
+    require!(max_conf_bps > 0 && max_conf_bps <= BASIS_POINTS, ErrorCode::InvalidMaxConfidence);
+
+    let config = &mut ctx.accounts.config;
+    let event = OracleUpdated {
+        authority: ctx.accounts.authority.key(),
+        old_feed_id: config.feed_id,
+        new_feed_id: feed_id,
+        old_max_price_age: config.max_price_age,
+        new_max_price_age: max_price_age,
+        old_max_conf_bps: config.max_conf_bps,
+        new_max_conf_bps: max_conf_bps,
+        old_price_mode: config.price_mode,
+        new_price_mode: price_mode,
+    };
+
+    config.feed_id = feed_id;
+    config.max_price_age = max_price_age;
+    config.max_conf_bps = max_conf_bps;
+    config.price_mode = price_mode;
+
+    emit_cpi!(event);
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, events::PsmParamsUpdated, state::Config, MAX_FEE_BPS};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdatePsm<'info> {
+    pub authority:Signer<'info>,
+    #[account(
+        mut,
+        seeds=[b"config"],
+        bump=config.bump,
+        has_one=authority @ ErrorCode::Unauthorized
+    )]
+    pub config:Account<'info,Config>,
+}
+
+// Sets the `swap_in` and `swap_out` fees in basis points and the max stablecoins the PSM
+// can mint. Lowering the ceiling below `psm_debt` only blocks `swap_in`.
+pub fn process_update_psm(
+    ctx:Context<UpdatePsm>,
+    fee_in_bps:u64,
+    fee_out_bps:u64,
+    debt_ceiling:u64,
+) -> Result<()> {
+    require!(fee_in_bps <= MAX_FEE_BPS && fee_out_bps <= MAX_FEE_BPS, ErrorCode::InvalidFee);
+
+    let config = &mut ctx.accounts.config;
+    let event = PsmParamsUpdated {
+        authority: ctx.accounts.authority.key(),
+        old_fee_in_bps: config.psm_fee_in_bps,
+        new_fee_in_bps: fee_in_bps,
+        old_fee_out_bps: config.psm_fee_out_bps,
+        new_fee_out_bps: fee_out_bps,
+        old_debt_ceiling: config.psm_debt_ceiling,
+        new_debt_ceiling: debt_ceiling,
+    };
+
+    config.psm_fee_in_bps = fee_in_bps;
+    config.psm_fee_out_bps = fee_out_bps;
+    config.psm_debt_ceiling = debt_ceiling;
+
+    emit_cpi!(event);
+    Ok(())
+}
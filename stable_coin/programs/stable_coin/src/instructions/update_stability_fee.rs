@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, events::StabilityFeeUpdated, state::Config, MAX_STABILITY_FEE_RATE};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateStabilityFee<'info> {
+    pub authority:Signer<'info>,
+    #[account(
+        mut,
+        seeds=[b"config"],
+        bump=config.bump,
+        has_one=authority @ ErrorCode::Unauthorized
+    )]
+    pub config:Account<'info,Config>,
+}
+
+// Sets the per second stability fee. Interest up to now is accrued at the old
+// rate first, so the new rate only applies from this slot on.
+pub fn process_update_stability_fee(ctx:Context<UpdateStabilityFee>, stability_fee_rate:u128) -> Result<()> {
+    require!(stability_fee_rate <= MAX_STABILITY_FEE_RATE, ErrorCode::InvalidStabilityFee);
+
+    let config = &mut ctx.accounts.config;
+    config.accrue_interest()?;
+
+    let event = StabilityFeeUpdated {
+        authority: ctx.accounts.authority.key(),
+        old_stability_fee_rate: config.stability_fee_rate,
+        new_stability_fee_rate: stability_fee_rate,
+        debt_index: config.debt_index,
+    };
+    config.stability_fee_rate = stability_fee_rate;
+
+    emit_cpi!(event);
+    Ok(())
+}
@@ -1,9 +1,10 @@
-use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+use anchor_lang::prelude::*;
 use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface}};
-use pyth_solana_receiver_sdk::price_update::{get_feed_id_from_hex, PriceUpdateV2};
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
 
-use crate::{burn_tokens, calculate_health_factor, error::ErrorCode, integer_usd_from_pyth, lamports_to_usd, state::{Collateral, Config}, usd_to_lamports, SOL_USDC_FEED_ID};
+use crate::{burn_tokens, calculate_health_factor, error::ErrorCode, events::{CollateralWithdrawn, Repaid}, fee_amount, lamports_to_usd, math::Rounding, oracle_price, state::{Collateral, Config, ProtocolState, Treasury}, transfer_from_vault, usd_to_lamports, PriceBound, PAUSE_WITHDRAW_BURN};
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct WithdrawBurn<'info>{
     #[account(mut)]
@@ -20,6 +21,12 @@ pub struct WithdrawBurn<'info>{
         bump
     )]
     pub config:Account<'info,Config>,
+    #[account(
+        mut,
+        seeds=[b"protocol_state"],
+        bump=protocol_state.bump
+    )]
+    pub protocol_state:Account<'info,ProtocolState>,
      #[account(
         mut,
         associated_token::mint=mint,
@@ -44,6 +51,12 @@ pub struct WithdrawBurn<'info>{
         bump
     )]
     pub mint:InterfaceAccount<'info,Mint>,
+    #[account(
+        mut,
+        seeds=[b"treasury"],
+        bump=treasury.bump
+    )]
+    pub treasury:Account<'info,Treasury>,
     pub price_update:Account<'info,PriceUpdateV2>,
     pub token_program:Interface<'info, TokenInterface>,
     pub system_program:Program<'info, System>,
@@ -56,38 +69,38 @@ pub struct WithdrawBurn<'info>{
 //  4. Transfer back the user's deposited funds from the sol account to the user's account.
 
 pub fn withdraw_burn(ctx:Context<WithdrawBurn>, withdraw_amount:u64)-> Result<()> {
+    require!(!ctx.accounts.config.is_paused(PAUSE_WITHDRAW_BURN), ErrorCode::ProtocolPaused);
+    require!(!ctx.accounts.withdrawer_collateral_account.in_auction, ErrorCode::PositionInAuction);
 
     let collateral_account = &mut ctx.accounts.withdrawer_collateral_account;
     let collateral_token_acc = &mut  ctx.accounts.withdraw_collateral_token_account;
     let price = &mut ctx.accounts.price_update;
     let config = &mut ctx.accounts.config;
+    config.accrue_interest()?;
     // fetching live price feeds
-    let clock = &Clock::get()?;
-    let feed_id = get_feed_id_from_hex(SOL_USDC_FEED_ID)?;
-    let price = price.get_price_no_older_than(clock, 3600, &feed_id)?;
-
-    let price_in_usd = integer_usd_from_pyth(price.price, price.exponent);
-    
-    let withdraw_amount_in_lamports = usd_to_lamports(withdraw_amount, (price_in_usd as u64))?;
-    let total_collateral_amount_in_usd = lamports_to_usd(collateral_account.lamports,(price_in_usd as u64))?;
-    
-    let new_coins_balance = collateral_account.coins.checked_sub(withdraw_amount).unwrap();
-    
-    msg!("BORROWED_AMOUNT:{}",collateral_account.coins);
-    msg!("NEW BORROWED_AMOUNT:{}", new_coins_balance);
+    // remaining collateral is valued at the low end of the confidence interval,
+    // while the payout uses the high end so the user never receives more SOL than burned
+    let price_in_usd = oracle_price(price, config, PriceBound::Lower)?;
+    let payout_price_in_usd = oracle_price(price, config, PriceBound::Upper)?;
     
-    msg!("Total Collateral Amount in Lamports:{}", collateral_account.lamports);
-    msg!("Total Collateral Amount in USD:{}", total_collateral_amount_in_usd);
+    let withdraw_amount_in_lamports = usd_to_lamports(withdraw_amount, payout_price_in_usd, Rounding::Down)?;
+    // handling max lamports edge case
+    let withdrawal_transfer_amount = withdraw_amount_in_lamports.min(collateral_account.lamports);
 
+    let repaid_coins = config.normalized_repayment(collateral_account.coins, withdraw_amount)?;
+    let new_coins_balance = collateral_account.coins.checked_sub(repaid_coins).ok_or(ErrorCode::MathOverflow)?;
+    let new_collateral_lamports = collateral_account.lamports.checked_sub(withdrawal_transfer_amount).ok_or(ErrorCode::MathOverflow)?;
+    let total_collateral_amount_in_usd = lamports_to_usd(new_collateral_lamports, price_in_usd, Rounding::Down)?;
+    
+    let new_debt = config.debt_of(new_coins_balance)?;
 
-    // calculating health factor
+    // calculating health factor of what is left after the withdrawal
     let health_factor = calculate_health_factor(
-        new_coins_balance,
+        new_debt,
          total_collateral_amount_in_usd,
          config.liq_thx
-        );
-    msg!("Health Factor : {}",health_factor);
-    if health_factor < 1 {
+        )?;
+    if health_factor < config.min_health_factor_wad()? {
         return Err(ErrorCode::HealthFactorError.into())
     }
     let signer = &mut ctx.accounts.withdrawer;
@@ -98,30 +111,49 @@ pub fn withdraw_burn(ctx:Context<WithdrawBurn>, withdraw_amount:u64)-> Result<()
         collateral_token_acc,
         withdraw_amount,
         signer
-        );
-    let mut withdrawal_transfer_amount:u64;
-    // handling max lamports edge case
-    if withdraw_amount_in_lamports > collateral_account.lamports {
-        withdrawal_transfer_amount = collateral_account.lamports;
-    }
-
-    withdrawal_transfer_amount = withdraw_amount_in_lamports;
+        )?;
 
-    // Transfer the equivalent collateral back to the user
-
-    let signer_seeds:&[&[&[u8]]] = &[&[b"collateral_token_account",ctx.accounts.withdrawer.key.as_ref(),&[ctx.bumps.withdraw_sol_account]]];
-
-    let context = CpiContext::new_with_signer(
-        ctx.accounts.system_program.to_account_info(),Transfer {
-        from:ctx.accounts.withdraw_sol_account.to_account_info(),
-        to:ctx.accounts.withdrawer.to_account_info()
-    },
-    signer_seeds);
-
-   transfer(context, withdrawal_transfer_amount)?;
+    // Transfer the equivalent collateral back to the user, less the redemption fee
+    let redemption_fee = fee_amount(withdrawal_transfer_amount, config.redemption_fee_bps)?;
+    transfer_from_vault(
+        &ctx.accounts.system_program,
+        &ctx.accounts.withdraw_sol_account,
+        &ctx.accounts.withdrawer.to_account_info(),
+        ctx.accounts.withdrawer.key,
+        ctx.bumps.withdraw_sol_account,
+        withdrawal_transfer_amount - redemption_fee
+    )?;
+    if redemption_fee > 0 {
+        transfer_from_vault(
+            &ctx.accounts.system_program,
+            &ctx.accounts.withdraw_sol_account,
+            &ctx.accounts.treasury.to_account_info(),
+            ctx.accounts.withdrawer.key,
+            ctx.bumps.withdraw_sol_account,
+            redemption_fee
+        )?;
+    }
    // Update the state of the user 
-    collateral_account.coins = collateral_account.coins.checked_sub(withdraw_amount).unwrap();
-    collateral_account.lamports = collateral_account.lamports.checked_sub(withdrawal_transfer_amount).unwrap();
+    collateral_account.coins = new_coins_balance;
+    config.remove_debt(repaid_coins)?;
+    collateral_account.lamports = new_collateral_lamports;
+
+    let protocol_state = &mut ctx.accounts.protocol_state;
+    protocol_state.remove_collateral(withdrawal_transfer_amount)?;
+    protocol_state.record_fee(lamports_to_usd(redemption_fee, payout_price_in_usd, Rounding::Down)?)?;
+    protocol_state.sync_debt(config)?;
 
+    let owner = ctx.accounts.withdrawer.key();
+    let position = ctx.accounts.withdrawer_collateral_account.key();
+    emit_cpi!(Repaid { payer: owner, position, amount: withdraw_amount, debt: new_debt });
+    emit_cpi!(CollateralWithdrawn {
+        owner,
+        position,
+        collateral_type: None,
+        amount: withdrawal_transfer_amount,
+        fee: redemption_fee,
+        price: payout_price_in_usd,
+        health_factor,
+    });
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+use crate::{calculate_health_factor, error::ErrorCode, events::CollateralWithdrawn, lamports_to_usd, math::Rounding, oracle_price, state::{Collateral, Config, ProtocolState}, transfer_from_vault, PriceBound, PAUSE_WITHDRAW_COLLATERAL};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawCollateral<'info>{
+    #[account(mut)]
+    pub withdrawer:Signer<'info>,
+    #[account(
+        mut,
+        seeds=[b"collateral", withdrawer.key().as_ref()],
+        bump=collateral_account.bump,
+        has_one=sol_account
+    )]
+    pub collateral_account:Account<'info,Collateral>,
+    #[account(
+        mut,
+        seeds=[b"config"],
+        bump=config.bump
+    )]
+    pub config:Account<'info,Config>,
+    #[account(
+        mut,
+        seeds=[b"protocol_state"],
+        bump=protocol_state.bump
+    )]
+    pub protocol_state:Account<'info,ProtocolState>,
+    /// SAFETY: This account is only used as a source for SOL transfers.
+    /// The seeds ensure that the PDA is derived deterministically and cannot be arbitrarily passed in by the client.
+    #[account(
+        mut,
+        seeds=[b"collateral_token_account",withdrawer.key().as_ref()],
+        bump=collateral_account.bump_sol_account
+    )]
+    pub sol_account: AccountInfo<'info>,
+    pub price_update:Account<'info,PriceUpdateV2>,
+    pub system_program:Program<'info, System>,
+}
+
+//  1. Make sure the user has enough collateral deposited
+//  2. Check the health factor of the position after the withdrawal
+//  3. Transfer the lamports from the sol account back to the user
+//  4. Reduce the user's collateral
+
+pub fn process_withdraw_collateral(ctx:Context<WithdrawCollateral>, lamports:u64) -> Result<()> {
+    require!(!ctx.accounts.config.is_paused(PAUSE_WITHDRAW_COLLATERAL), ErrorCode::ProtocolPaused);
+    require!(!ctx.accounts.collateral_account.in_auction, ErrorCode::PositionInAuction);
+    require!(lamports > 0, ErrorCode::InvalidAmount);
+
+    let collateral_account = &mut ctx.accounts.collateral_account;
+    let config = &mut ctx.accounts.config;
+    config.accrue_interest()?;
+
+    // 1. check the deposited collateral
+    let new_collateral_lamports = collateral_account.lamports
+        .checked_sub(lamports)
+        .ok_or(ErrorCode::InsufficientCollateral)?;
+
+    // 2. value what is left at the low end of the price and check the health factor
+    let price_in_usd = oracle_price(&ctx.accounts.price_update, config, PriceBound::Lower)?;
+    let remaining_collateral_in_usd = lamports_to_usd(new_collateral_lamports, price_in_usd, Rounding::Down)?;
+    let health_factor = calculate_health_factor(
+        config.debt_of(collateral_account.coins)?,
+        remaining_collateral_in_usd,
+        config.liq_thx
+    )?;
+    if health_factor < config.min_health_factor_wad()? {
+        return Err(ErrorCode::HealthFactorError.into())
+    }
+
+    // 3. transfer the collateral back to the user
+    transfer_from_vault(
+        &ctx.accounts.system_program,
+        &ctx.accounts.sol_account,
+        &ctx.accounts.withdrawer.to_account_info(),
+        ctx.accounts.withdrawer.key,
+        collateral_account.bump_sol_account,
+        lamports
+    )?;
+
+    // 4. update the user's collateral
+    collateral_account.lamports = new_collateral_lamports;
+
+    let protocol_state = &mut ctx.accounts.protocol_state;
+    protocol_state.remove_collateral(lamports)?;
+    protocol_state.sync_debt(config)?;
+
+    emit_cpi!(CollateralWithdrawn {
+        owner: ctx.accounts.withdrawer.key(),
+        position: collateral_account.key(),
+        collateral_type: None,
+        amount: lamports,
+        fee: 0,
+        price: price_in_usd,
+        health_factor,
+    });
+    Ok(())
+}
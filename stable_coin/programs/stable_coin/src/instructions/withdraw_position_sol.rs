@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+use crate::{assign_position_debt, error::ErrorCode, events::CollateralWithdrawn, health_factor_from_weighted, position_debt_shares, state::{Config, Position, ProtocolState}, transfer_from_position_vault, value_position, PriceBound, PAUSE_WITHDRAW_COLLATERAL};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawPositionSol<'info> {
+    #[account(mut)]
+    pub owner:Signer<'info>,
+    #[account(
+        mut,
+        seeds=[b"config"],
+        bump=config.bump
+    )]
+    pub config:Account<'info,Config>,
+    #[account(
+        mut,
+        seeds=[b"protocol_state"],
+        bump=protocol_state.bump
+    )]
+    pub protocol_state:Account<'info,ProtocolState>,
+    #[account(
+        mut,
+        seeds=[b"position", owner.key().as_ref()],
+        bump=position.bump
+    )]
+    pub position:Account<'info,Position>,
+    /// SAFETY: This account is only used as a recipient for SOL transfers.
+    /// The seeds ensure that the PDA is derived deterministically and cannot be arbitrarily passed in by the client.
+    #[account(
+        mut,
+        seeds=[b"position_sol_account", owner.key().as_ref()],
+        bump=position.bump_sol_account
+    )]
+    pub position_sol_account:AccountInfo<'info>,
+    // SOL/USD price, the other assets are priced through `remaining_accounts`
+    pub price_update:Account<'info,PriceUpdateV2>,
+    pub system_program:Program<'info,System>,
+}
+
+//  1. Reduce the position's SOL balance
+//  2. Check the health factor of every asset left against the debt
+//  3. Split the debt again across the SPL balances, which now back more of it
+//  4. Transfer the SOL back to the owner
+
+pub fn process_withdraw_position_sol(ctx:Context<WithdrawPositionSol>, amount:u64) -> Result<()> {
+    require!(!ctx.accounts.config.is_paused(PAUSE_WITHDRAW_COLLATERAL), ErrorCode::ProtocolPaused);
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    let config = &mut ctx.accounts.config;
+    config.accrue_interest()?;
+    let position = &mut ctx.accounts.position;
+
+    // 1. reduce the SOL balance
+    position.lamports = position.lamports.checked_sub(amount).ok_or(ErrorCode::InsufficientCollateral)?;
+
+    // 2. what is left is valued at the low end of every price
+    let value = value_position(position, config, &ctx.accounts.price_update, ctx.remaining_accounts, PriceBound::Lower)?;
+    let health_factor = health_factor_from_weighted(config.debt_of(position.coins)?, value.weighted_collateral)?;
+    if health_factor < config.min_health_factor_wad()? {
+        return Err(ErrorCode::HealthFactorError.into())
+    }
+
+    // 3. the SPL balances take over the share the SOL backed, within their debt ceilings
+    let shares = position_debt_shares(position.coins, &value)?;
+    assign_position_debt(position, config, ctx.remaining_accounts.iter().step_by(2), &shares)?;
+
+    // 4. transfer the SOL back to the owner
+    transfer_from_position_vault(
+        &ctx.accounts.system_program,
+        &ctx.accounts.position_sol_account,
+        &ctx.accounts.owner.to_account_info(),
+        ctx.accounts.owner.key,
+        position.bump_sol_account,
+        amount
+    )?;
+
+    let protocol_state = &mut ctx.accounts.protocol_state;
+    protocol_state.remove_collateral(amount)?;
+    protocol_state.sync_debt(config)?;
+
+    emit_cpi!(CollateralWithdrawn {
+        owner: ctx.accounts.owner.key(),
+        position: position.key(),
+        collateral_type: None,
+        amount,
+        fee: 0,
+        price: value.sol_price,
+        health_factor,
+    });
+    Ok(())
+}
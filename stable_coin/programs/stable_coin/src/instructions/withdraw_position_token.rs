@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+use crate::{assign_position_debt, error::ErrorCode, events::CollateralWithdrawn, health_factor_from_weighted, position_debt_shares, state::{CollateralType, Config, Position, ProtocolState}, transfer_from_collateral_vault, value_position, PriceBound, PAUSE_WITHDRAW_COLLATERAL};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawPositionToken<'info> {
+    #[account(mut)]
+    pub owner:Signer<'info>,
+    #[account(
+        mut,
+        seeds=[b"config"],
+        bump=config.bump
+    )]
+    pub config:Account<'info,Config>,
+    #[account(
+        mut,
+        seeds=[b"protocol_state"],
+        bump=protocol_state.bump
+    )]
+    pub protocol_state:Account<'info,ProtocolState>,
+    #[account(
+        mut,
+        seeds=[b"position", owner.key().as_ref()],
+        bump=position.bump
+    )]
+    pub position:Account<'info,Position>,
+    #[account(
+        seeds=[b"collateral_type", collateral_mint.key().as_ref()],
+        bump=collateral_type.bump,
+        has_one=vault
+    )]
+    pub collateral_type:Account<'info,CollateralType>,
+    #[account(
+        mut,
+        token::mint=collateral_mint,
+        token::authority=owner,
+        token::token_program=collateral_token_program
+    )]
+    pub owner_collateral_account:InterfaceAccount<'info,TokenAccount>,
+    #[account(mut)]
+    pub vault:InterfaceAccount<'info,TokenAccount>,
+    pub collateral_mint:InterfaceAccount<'info,Mint>,
+    // SOL/USD price, the other assets are priced through `remaining_accounts`
+    pub price_update:Account<'info,PriceUpdateV2>,
+    pub collateral_token_program:Interface<'info,TokenInterface>,
+}
+
+//  1. Reduce the position's balance of this collateral type
+//  2. Check the health factor of every asset left against the debt
+//  3. Split the debt again across the SPL balances by what each one is now worth
+//  4. Transfer the tokens from the vault back to the owner
+//  5. Drop the balance from the position once it is empty
+
+pub fn process_withdraw_position_token(ctx:Context<WithdrawPositionToken>, amount:u64) -> Result<()> {
+    require!(!ctx.accounts.config.is_paused(PAUSE_WITHDRAW_COLLATERAL), ErrorCode::ProtocolPaused);
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    let config = &mut ctx.accounts.config;
+    config.accrue_interest()?;
+    let position = &mut ctx.accounts.position;
+
+    // 1. reduce the balance, it stays in the list until the health check so
+    // `remaining_accounts` always follows the balances as they were before the call
+    let index = position.balance_index(&ctx.accounts.collateral_type.key()).ok_or(ErrorCode::CollateralNotInPosition)?;
+    let balance = &mut position.balances[index];
+    balance.amount = balance.amount.checked_sub(amount).ok_or(ErrorCode::InsufficientCollateral)?;
+
+    // 2. what is left is valued at the low end of every price
+    let value = value_position(position, config, &ctx.accounts.price_update, ctx.remaining_accounts, PriceBound::Lower)?;
+    let health_factor = health_factor_from_weighted(config.debt_of(position.coins)?, value.weighted_collateral)?;
+    if health_factor < config.min_health_factor_wad()? {
+        return Err(ErrorCode::HealthFactorError.into())
+    }
+
+    // 3. an emptied balance gives its whole share back to its collateral type
+    let shares = position_debt_shares(position.coins, &value)?;
+    assign_position_debt(position, config, ctx.remaining_accounts.iter().step_by(2), &shares)?;
+
+    // 4. transfer the collateral back to the owner
+    transfer_from_collateral_vault(
+        &ctx.accounts.collateral_token_program,
+        &ctx.accounts.vault,
+        &ctx.accounts.owner_collateral_account,
+        &ctx.accounts.collateral_mint,
+        &ctx.accounts.collateral_type,
+        amount
+    )?;
+
+    // 5. free the slot of an emptied balance
+    if position.balances[index].amount == 0 {
+        position.balances.remove(index);
+    }
+    ctx.accounts.protocol_state.sync_debt(config)?;
+
+    emit_cpi!(CollateralWithdrawn {
+        owner: ctx.accounts.owner.key(),
+        position: position.key(),
+        collateral_type: Some(ctx.accounts.collateral_type.key()),
+        amount,
+        fee: 0,
+        price: value.token_prices[index],
+        health_factor,
+    });
+    Ok(())
+}
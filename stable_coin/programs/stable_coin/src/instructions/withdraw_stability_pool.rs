@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked}};
+
+use crate::{error::ErrorCode, events::StabilityPoolWithdrawn, state::{Config, PoolDeposit, StabilityPool}, PAUSE_STABILITY_POOL, STABILITY_POOL_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawStabilityPool<'info> {
+    #[account(mut)]
+    pub depositor:Signer<'info>,
+    #[account(
+        mut,
+        associated_token::mint=mint,
+        associated_token::authority=depositor,
+        associated_token::token_program=token_program
+    )]
+    pub depositor_token_account:InterfaceAccount<'info,TokenAccount>,
+    #[account(
+        mut,
+        seeds=[b"pool_deposit", depositor.key().as_ref()],
+        bump=pool_deposit.bump,
+        has_one=depositor
+    )]
+    pub pool_deposit:Account<'info,PoolDeposit>,
+    #[account(
+        mut,
+        seeds=[b"stability_pool"],
+        bump=stability_pool.bump,
+        has_one=token_account
+    )]
+    pub stability_pool:Account<'info,StabilityPool>,
+    #[account(mut)]
+    pub token_account:InterfaceAccount<'info,TokenAccount>,
+    #[account(
+        seeds=[b"config"],
+        bump=config.bump
+    )]
+    pub config:Account<'info,Config>,
+    #[account(
+        seeds=[b"jacked_nerd"],
+        bump=config.bump_mint_acc
+    )]
+    pub mint:InterfaceAccount<'info,Mint>,
+    pub token_program:Interface<'info,TokenInterface>,
+    pub associated_token_program:Program<'info,AssociatedToken>
+}
+
+//  1. Pay out the SOL the deposit earned and compound it
+//  2. Return up to `amount` of what is left of the deposit, zero only claims the SOL gains
+//  3. Snapshot the pool for the remaining deposit
+
+pub fn process_withdraw_stability_pool(ctx:Context<WithdrawStabilityPool>, amount:u64) -> Result<()> {
+    require!(!ctx.accounts.config.is_paused(PAUSE_STABILITY_POOL), ErrorCode::ProtocolPaused);
+
+    let pool = &mut ctx.accounts.stability_pool;
+    let pool_deposit = &mut ctx.accounts.pool_deposit;
+
+    // 1. the pool is owned by this program, so its SOL gains are moved directly
+    let compounded = pool.compounded_deposit(pool_deposit)?;
+    let collateral_gain = pool.collateral_gain(pool_deposit)?;
+    if collateral_gain > 0 {
+        **pool.to_account_info().try_borrow_mut_lamports()? -= collateral_gain;
+        **ctx.accounts.depositor.to_account_info().try_borrow_mut_lamports()? += collateral_gain;
+    }
+
+    // 2. the pool PDA signs for its token account
+    let withdrawn = amount.min(compounded);
+    if withdrawn > 0 {
+        let signer_seeds:&[&[&[u8]]] = &[&[STABILITY_POOL_SEED, &[pool.bump]]];
+        let context = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from:ctx.accounts.token_account.to_account_info(),
+                mint:ctx.accounts.mint.to_account_info(),
+                to:ctx.accounts.depositor_token_account.to_account_info(),
+                authority:pool.to_account_info(),
+            },
+            signer_seeds);
+        transfer_checked(context, withdrawn, ctx.accounts.mint.decimals)?;
+    }
+
+    // 3. snapshot the pool for what stays in
+    let deposit = compounded - withdrawn;
+    pool.total_deposits = pool.total_deposits.checked_sub(withdrawn).ok_or(ErrorCode::MathOverflow)?;
+    pool_deposit.snapshot(deposit, pool);
+
+    emit_cpi!(StabilityPoolWithdrawn {
+        depositor: ctx.accounts.depositor.key(),
+        amount: withdrawn,
+        deposit,
+        collateral_gain,
+    });
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+use crate::{calculate_health_factor, error::ErrorCode, events::CollateralWithdrawn, feed_price, math::Rounding, state::{CollateralType, Config, ProtocolState, TokenCollateral}, token_to_usd, transfer_from_collateral_vault, PriceBound, PAUSE_WITHDRAW_COLLATERAL};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawTokenCollateral<'info> {
+    #[account(mut)]
+    pub withdrawer:Signer<'info>,
+    #[account(
+        mut,
+        seeds=[b"config"],
+        bump=config.bump
+    )]
+    pub config:Account<'info,Config>,
+    #[account(
+        mut,
+        seeds=[b"protocol_state"],
+        bump=protocol_state.bump
+    )]
+    pub protocol_state:Account<'info,ProtocolState>,
+    #[account(
+        seeds=[b"collateral_type", collateral_mint.key().as_ref()],
+        bump=collateral_type.bump,
+        has_one=vault
+    )]
+    pub collateral_type:Account<'info,CollateralType>,
+    #[account(
+        mut,
+        seeds=[b"collateral", withdrawer.key().as_ref(), collateral_type.key().as_ref()],
+        bump=token_collateral.bump
+    )]
+    pub token_collateral:Account<'info,TokenCollateral>,
+    #[account(
+        mut,
+        token::mint=collateral_mint,
+        token::authority=withdrawer,
+        token::token_program=collateral_token_program
+    )]
+    pub withdrawer_collateral_account:InterfaceAccount<'info,TokenAccount>,
+    #[account(mut)]
+    pub vault:InterfaceAccount<'info,TokenAccount>,
+    pub collateral_mint:InterfaceAccount<'info,Mint>,
+    pub price_update:Account<'info,PriceUpdateV2>,
+    pub collateral_token_program:Interface<'info,TokenInterface>,
+}
+
+//  1. Make sure the user has enough collateral deposited
+//  2. Check the health factor of the position after the withdrawal
+//  3. Transfer the tokens from the vault back to the user
+//  4. Reduce the user's collateral
+
+pub fn process_withdraw_token_collateral(ctx:Context<WithdrawTokenCollateral>, amount:u64) -> Result<()> {
+    require!(!ctx.accounts.config.is_paused(PAUSE_WITHDRAW_COLLATERAL), ErrorCode::ProtocolPaused);
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    let config = &mut ctx.accounts.config;
+    config.accrue_interest()?;
+    let collateral_type = &ctx.accounts.collateral_type;
+    let position = &mut ctx.accounts.token_collateral;
+
+    // 1. check the deposited collateral
+    let new_amount = position.amount.checked_sub(amount).ok_or(ErrorCode::InsufficientCollateral)?;
+
+    // 2. value what is left at the low end of the price and check the health factor
+    let price_in_usd = feed_price(&ctx.accounts.price_update, config, &collateral_type.feed_id, PriceBound::Lower)?;
+    let remaining_collateral_in_usd = token_to_usd(new_amount, collateral_type.decimals, price_in_usd, Rounding::Down)?;
+    let health_factor = calculate_health_factor(config.debt_of(position.coins)?, remaining_collateral_in_usd, collateral_type.liq_thx)?;
+    if health_factor < config.min_health_factor_wad()? {
+        return Err(ErrorCode::HealthFactorError.into())
+    }
+
+    // 3. transfer the collateral back to the user
+    transfer_from_collateral_vault(
+        &ctx.accounts.collateral_token_program,
+        &ctx.accounts.vault,
+        &ctx.accounts.withdrawer_collateral_account,
+        &ctx.accounts.collateral_mint,
+        collateral_type,
+        amount
+    )?;
+
+    // 4. update the user's collateral
+    position.amount = new_amount;
+    ctx.accounts.protocol_state.sync_debt(config)?;
+
+    emit_cpi!(CollateralWithdrawn {
+        owner: ctx.accounts.withdrawer.key(),
+        position: position.key(),
+        collateral_type: Some(collateral_type.key()),
+        amount,
+        fee: 0,
+        price: price_in_usd,
+        health_factor,
+    });
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{error::ErrorCode, events::TreasuryWithdrawn, mint_surplus, state::{Config, ProtocolState, Treasury}, TREASURY_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    pub authority:Signer<'info>,
+    #[account(
+        mut,
+        seeds=[b"config"],
+        bump=config.bump,
+        has_one=authority @ ErrorCode::Unauthorized
+    )]
+    pub config:Account<'info,Config>,
+    #[account(
+        mut,
+        seeds=[b"protocol_state"],
+        bump=protocol_state.bump
+    )]
+    pub protocol_state:Account<'info,ProtocolState>,
+    #[account(
+        mut,
+        seeds=[b"treasury"],
+        bump=treasury.bump,
+        has_one=token_account
+    )]
+    pub treasury:Account<'info,Treasury>,
+    #[account(mut)]
+    pub token_account:InterfaceAccount<'info,TokenAccount>,
+    /// SAFETY: This account is only used as a recipient for SOL transfers, chosen by the authority.
+    #[account(mut)]
+    pub recipient:AccountInfo<'info>,
+    #[account(
+        mut,
+        token::mint=mint,
+        token::token_program=token_program
+    )]
+    pub recipient_token_account:InterfaceAccount<'info,TokenAccount>,
+    #[account(
+        mut,
+        seeds=[b"jacked_nerd"],
+        bump=config.bump_mint_acc
+    )]
+    pub mint:InterfaceAccount<'info,Mint>,
+    pub token_program:Interface<'info,TokenInterface>,
+}
+
+// 1. Mint the stability fees accrued since the last collection into the treasury
+// 2. Pay out SOL held on the treasury account, never touching its rent exempt reserve
+// 3. Pay out stablecoins from the treasury token account
+// 4. Emit the payout
+
+pub fn process_withdraw_treasury(ctx:Context<WithdrawTreasury>, lamports:u64, coins:u64) -> Result<()> {
+    require!(lamports > 0 || coins > 0, ErrorCode::InvalidAmount);
+
+    // 1. the booked surplus becomes stablecoins the treasury can pay out
+    let config = &mut ctx.accounts.config;
+    config.accrue_interest()?;
+    mint_surplus(config, &mut ctx.accounts.protocol_state, &ctx.accounts.token_account, &ctx.accounts.token_program, &ctx.accounts.mint)?;
+    ctx.accounts.token_account.reload()?;
+
+    // 2. the treasury is owned by this program, so lamports are moved directly
+    if lamports > 0 {
+        let treasury = ctx.accounts.treasury.to_account_info();
+        let reserve = Rent::get()?.minimum_balance(treasury.data_len());
+        let available = treasury.lamports().saturating_sub(reserve);
+        require!(lamports <= available, ErrorCode::InsufficientTreasuryBalance);
+
+        **treasury.try_borrow_mut_lamports()? -= lamports;
+        **ctx.accounts.recipient.try_borrow_mut_lamports()? += lamports;
+    }
+
+    // 3. stablecoins are signed for by the treasury PDA
+    if coins > 0 {
+        require!(coins <= ctx.accounts.token_account.amount, ErrorCode::InsufficientTreasuryBalance);
+        let signer_seeds:&[&[&[u8]]] = &[&[TREASURY_SEED, &[ctx.accounts.treasury.bump]]];
+        let context = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from:ctx.accounts.token_account.to_account_info(),
+                mint:ctx.accounts.mint.to_account_info(),
+                to:ctx.accounts.recipient_token_account.to_account_info(),
+                authority:ctx.accounts.treasury.to_account_info(),
+            },
+            signer_seeds);
+        transfer_checked(context, coins, ctx.accounts.mint.decimals)?;
+    }
+
+    // 4. emit the payout
+    emit_cpi!(TreasuryWithdrawn {
+        authority: ctx.accounts.authority.key(),
+        recipient: ctx.accounts.recipient.key(),
+        lamports,
+        coins,
+    });
+    Ok(())
+}
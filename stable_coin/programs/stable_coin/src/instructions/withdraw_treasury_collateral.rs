@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{error::ErrorCode, events::TreasuryCollateralWithdrawn, state::{Config, Treasury}, TREASURY_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawTreasuryCollateral<'info> {
+    pub authority:Signer<'info>,
+    #[account(
+        seeds=[b"config"],
+        bump=config.bump,
+        has_one=authority @ ErrorCode::Unauthorized
+    )]
+    pub config:Account<'info,Config>,
+    #[account(
+        seeds=[b"treasury"],
+        bump=treasury.bump
+    )]
+    pub treasury:Account<'info,Treasury>,
+    #[account(
+        mut,
+        seeds=[b"treasury_collateral", collateral_mint.key().as_ref()],
+        bump
+    )]
+    pub treasury_collateral_account:InterfaceAccount<'info,TokenAccount>,
+    #[account(
+        mut,
+        token::mint=collateral_mint,
+        token::token_program=collateral_token_program
+    )]
+    pub recipient_token_account:InterfaceAccount<'info,TokenAccount>,
+    pub collateral_mint:InterfaceAccount<'info,Mint>,
+    pub collateral_token_program:Interface<'info,TokenInterface>,
+}
+
+// Pays out the SPL collateral the treasury collected as liquidation fees on one collateral mint,
+// signed for by the treasury PDA.
+pub fn process_withdraw_treasury_collateral(ctx:Context<WithdrawTreasuryCollateral>, amount:u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    require!(amount <= ctx.accounts.treasury_collateral_account.amount, ErrorCode::InsufficientTreasuryBalance);
+
+    let signer_seeds:&[&[&[u8]]] = &[&[TREASURY_SEED, &[ctx.accounts.treasury.bump]]];
+    let context = CpiContext::new_with_signer(
+        ctx.accounts.collateral_token_program.to_account_info(),
+        TransferChecked {
+            from:ctx.accounts.treasury_collateral_account.to_account_info(),
+            mint:ctx.accounts.collateral_mint.to_account_info(),
+            to:ctx.accounts.recipient_token_account.to_account_info(),
+            authority:ctx.accounts.treasury.to_account_info(),
+        },
+        signer_seeds);
+    transfer_checked(context, amount, ctx.accounts.collateral_mint.decimals)?;
+
+    emit_cpi!(TreasuryCollateralWithdrawn {
+        authority: ctx.accounts.authority.key(),
+        recipient: ctx.accounts.recipient_token_account.key(),
+        mint: ctx.accounts.collateral_mint.key(),
+        amount,
+    });
+    Ok(())
+}
@@ -1,6 +1,8 @@
 pub mod constants;
 pub mod error;
+pub mod events;
 pub mod instructions;
+pub mod math;
 pub mod state;
 
 use anchor_lang::prelude::*;
@@ -24,16 +26,258 @@ pub mod stable_coin {
     Ok(())
     }
 
+    pub fn update_config(
+        ctx:Context<UpdateConfig>,
+        liq_thx:u64,
+        liq_bonus:u64,
+        min_health_factor:u64,
+        close_factor:u64,
+    ) -> Result<()>{
+        instructions::process_update_config(ctx, liq_thx, liq_bonus, min_health_factor, close_factor)?;
+        Ok(())
+    }
+
+    pub fn update_oracle(
+        ctx:Context<UpdateOracle>,
+        feed_id:[u8;32],
+        max_price_age:u64,
+        max_conf_bps:u64,
+        price_mode:state::PriceMode,
+    ) -> Result<()>{
+        instructions::process_update_oracle(ctx, feed_id, max_price_age, max_conf_bps, price_mode)?;
+        Ok(())
+    }
+
+    pub fn update_stability_fee(ctx:Context<UpdateStabilityFee>, stability_fee_rate:u128) -> Result<()>{
+        instructions::process_update_stability_fee(ctx, stability_fee_rate)?;
+        Ok(())
+    }
+
+    pub fn update_debt_ceilings(
+        ctx:Context<UpdateDebtCeilings>,
+        global_debt_ceiling:u64,
+        max_debt_per_position:u64,
+    ) -> Result<()>{
+        instructions::process_update_debt_ceilings(ctx, global_debt_ceiling, max_debt_per_position)?;
+        Ok(())
+    }
+
+    pub fn update_auction_params(
+        ctx:Context<UpdateAuctionParams>,
+        auction_buffer_bps:u64,
+        auction_duration:i64,
+        auction_floor_bps:u64,
+    ) -> Result<()>{
+        instructions::process_update_auction_params(ctx, auction_buffer_bps, auction_duration, auction_floor_bps)?;
+        Ok(())
+    }
+
+    pub fn init_treasury(ctx:Context<InitTreasury>) -> Result<()>{
+        instructions::process_init_treasury(ctx)?;
+        Ok(())
+    }
+
+    pub fn update_fees(
+        ctx:Context<UpdateFees>,
+        mint_fee_bps:u64,
+        redemption_fee_bps:u64,
+        liquidation_fee_bps:u64,
+    ) -> Result<()>{
+        instructions::process_update_fees(ctx, mint_fee_bps, redemption_fee_bps, liquidation_fee_bps)?;
+        Ok(())
+    }
+
+    pub fn withdraw_treasury(ctx:Context<WithdrawTreasury>, lamports:u64, coins:u64) -> Result<()>{
+        instructions::process_withdraw_treasury(ctx, lamports, coins)?;
+        Ok(())
+    }
+
+    pub fn withdraw_treasury_collateral(ctx:Context<WithdrawTreasuryCollateral>, amount:u64) -> Result<()>{
+        instructions::process_withdraw_treasury_collateral(ctx, amount)?;
+        Ok(())
+    }
+
+    pub fn propose_authority(ctx:Context<ProposeAuthority>, new_authority:Pubkey) -> Result<()>{
+        instructions::process_propose_authority(ctx, new_authority)?;
+        Ok(())
+    }
+
+    pub fn accept_authority(ctx:Context<AcceptAuthority>) -> Result<()>{
+        instructions::process_accept_authority(ctx)?;
+        Ok(())
+    }
+
+    pub fn renounce_authority(ctx:Context<RenounceAuthority>) -> Result<()>{
+        instructions::process_renounce_authority(ctx)?;
+        Ok(())
+    }
+
+    pub fn set_guardian(ctx:Context<SetGuardian>, guardian:Option<Pubkey>) -> Result<()>{
+        instructions::process_set_guardian(ctx, guardian)?;
+        Ok(())
+    }
+
+    pub fn set_pause(ctx:Context<SetPause>, paused_flags:u32) -> Result<()>{
+        instructions::process_set_pause(ctx, paused_flags)?;
+        Ok(())
+    }
+
     pub fn deposit_and_mint_tokens(ctx: Context<InitDeposit>,amount:u64) -> Result<()> {
         instructions::process_deposit(ctx, amount)?;
         Ok(())
     }
+    pub fn deposit_collateral(ctx: Context<DepositCollateral>,lamports:u64) -> Result<()> {
+        instructions::process_deposit_collateral(ctx, lamports)?;
+        Ok(())
+    }
+    pub fn mint_stable(ctx: Context<MintStable>,amount:u64) -> Result<()> {
+        instructions::process_mint_stable(ctx, amount)?;
+        Ok(())
+    }
     pub fn withdraw_burn(ctx:Context<WithdrawBurn>,withdraw_amount:u64)-> Result<()>{
         instructions::withdraw_burn(ctx, withdraw_amount)?;
         Ok(())
     }
+    pub fn repay(ctx:Context<Repay>,amount:u64)-> Result<()>{
+        instructions::process_repay(ctx, amount)?;
+        Ok(())
+    }
+    pub fn withdraw_collateral(ctx:Context<WithdrawCollateral>,lamports:u64)-> Result<()>{
+        instructions::process_withdraw_collateral(ctx, lamports)?;
+        Ok(())
+    }
+    pub fn close_position(ctx:Context<ClosePosition>)-> Result<()>{
+        instructions::process_close_position(ctx)?;
+        Ok(())
+    }
+    pub fn get_position_health(ctx:Context<GetPositionHealth>) -> Result<PositionHealth>{
+        instructions::process_get_position_health(ctx)
+    }
     pub fn liquidate(ctx:Context<Liquidate>,coin_amount:u64)-> Result<()>{
         instructions::process_liquidate(ctx, coin_amount)?;
         Ok(())
     }
+    pub fn start_auction(ctx:Context<StartAuction>)-> Result<()>{
+        instructions::process_start_auction(ctx)?;
+        Ok(())
+    }
+    pub fn bid_auction(ctx:Context<BidAuction>,lamports:u64,max_price:u128)-> Result<()>{
+        instructions::process_bid_auction(ctx, lamports, max_price)?;
+        Ok(())
+    }
+
+    pub fn init_stability_pool(ctx:Context<InitStabilityPool>) -> Result<()>{
+        instructions::process_init_stability_pool(ctx)?;
+        Ok(())
+    }
+    pub fn deposit_stability_pool(ctx:Context<DepositStabilityPool>,amount:u64) -> Result<()>{
+        instructions::process_deposit_stability_pool(ctx, amount)?;
+        Ok(())
+    }
+    pub fn withdraw_stability_pool(ctx:Context<WithdrawStabilityPool>,amount:u64) -> Result<()>{
+        instructions::process_withdraw_stability_pool(ctx, amount)?;
+        Ok(())
+    }
+    pub fn liquidate_with_pool(ctx:Context<LiquidateWithPool>,coin_amount:u64) -> Result<()>{
+        instructions::process_liquidate_with_pool(ctx, coin_amount)?;
+        Ok(())
+    }
+    pub fn settle_bad_debt(ctx:Context<SettleBadDebt>,socialize:bool) -> Result<()>{
+        instructions::process_settle_bad_debt(ctx, socialize)?;
+        Ok(())
+    }
+    pub fn redeem<'info>(ctx:Context<'_, '_, 'info, 'info, Redeem<'info>>,amount:u64,max_fee_bps:u64) -> Result<()>{
+        instructions::process_redeem(ctx, amount, max_fee_bps)?;
+        Ok(())
+    }
+
+    pub fn init_psm(ctx:Context<InitPsm>) -> Result<()>{
+        instructions::process_init_psm(ctx)?;
+        Ok(())
+    }
+    pub fn update_psm(
+        ctx:Context<UpdatePsm>,
+        fee_in_bps:u64,
+        fee_out_bps:u64,
+        debt_ceiling:u64,
+    ) -> Result<()>{
+        instructions::process_update_psm(ctx, fee_in_bps, fee_out_bps, debt_ceiling)?;
+        Ok(())
+    }
+    pub fn swap_in(ctx:Context<SwapIn>,amount:u64) -> Result<()>{
+        instructions::process_swap_in(ctx, amount)?;
+        Ok(())
+    }
+    pub fn swap_out(ctx:Context<SwapOut>,amount:u64) -> Result<()>{
+        instructions::process_swap_out(ctx, amount)?;
+        Ok(())
+    }
+
+    pub fn add_collateral_type(
+        ctx:Context<AddCollateralType>,
+        feed_id:[u8;32],
+        liq_thx:u64,
+        liq_bonus:u64,
+        debt_ceiling:u64,
+    ) -> Result<()>{
+        instructions::process_add_collateral_type(ctx, feed_id, liq_thx, liq_bonus, debt_ceiling)?;
+        Ok(())
+    }
+    pub fn deposit_token_collateral(ctx:Context<DepositTokenCollateral>,amount:u64) -> Result<()>{
+        instructions::process_deposit_token_collateral(ctx, amount)?;
+        Ok(())
+    }
+    pub fn withdraw_token_collateral(ctx:Context<WithdrawTokenCollateral>,amount:u64) -> Result<()>{
+        instructions::process_withdraw_token_collateral(ctx, amount)?;
+        Ok(())
+    }
+    pub fn mint_against_token_collateral(ctx:Context<MintAgainstTokenCollateral>,amount:u64) -> Result<()>{
+        instructions::process_mint_against_token_collateral(ctx, amount)?;
+        Ok(())
+    }
+    pub fn repay_token_collateral(ctx:Context<RepayTokenCollateral>,amount:u64) -> Result<()>{
+        instructions::process_repay_token_collateral(ctx, amount)?;
+        Ok(())
+    }
+    pub fn liquidate_token_collateral(ctx:Context<LiquidateTokenCollateral>,coin_amount:u64) -> Result<()>{
+        instructions::process_liquidate_token_collateral(ctx, coin_amount)?;
+        Ok(())
+    }
+
+    pub fn open_position(ctx:Context<OpenPosition>) -> Result<()>{
+        instructions::process_open_position(ctx)?;
+        Ok(())
+    }
+    pub fn deposit_position_sol(ctx:Context<DepositPositionSol>,amount:u64) -> Result<()>{
+        instructions::process_deposit_position_sol(ctx, amount)?;
+        Ok(())
+    }
+    pub fn deposit_position_token(ctx:Context<DepositPositionToken>,amount:u64) -> Result<()>{
+        instructions::process_deposit_position_token(ctx, amount)?;
+        Ok(())
+    }
+    pub fn withdraw_position_sol(ctx:Context<WithdrawPositionSol>,amount:u64) -> Result<()>{
+        instructions::process_withdraw_position_sol(ctx, amount)?;
+        Ok(())
+    }
+    pub fn withdraw_position_token(ctx:Context<WithdrawPositionToken>,amount:u64) -> Result<()>{
+        instructions::process_withdraw_position_token(ctx, amount)?;
+        Ok(())
+    }
+    pub fn mint_position(ctx:Context<MintPosition>,amount:u64) -> Result<()>{
+        instructions::process_mint_position(ctx, amount)?;
+        Ok(())
+    }
+    pub fn repay_position(ctx:Context<RepayPosition>,amount:u64) -> Result<()>{
+        instructions::process_repay_position(ctx, amount)?;
+        Ok(())
+    }
+    pub fn liquidate_position_sol(ctx:Context<LiquidatePositionSol>,coin_amount:u64) -> Result<()>{
+        instructions::process_liquidate_position_sol(ctx, coin_amount)?;
+        Ok(())
+    }
+    pub fn liquidate_position_token(ctx:Context<LiquidatePositionToken>,coin_amount:u64) -> Result<()>{
+        instructions::process_liquidate_position_token(ctx, coin_amount)?;
+        Ok(())
+    }
 }
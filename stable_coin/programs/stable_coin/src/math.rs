@@ -0,0 +1,179 @@
+// Fixed-point helpers for prices and token amounts.
+//
+// Prices are stored as WAD-scaled (1e18) u128 values, so $143.99 is
+// 143_990_000_000_000_000_000. Token amounts stay in base units: lamports for
+// SOL and 9-decimal base units for the stablecoin. Every operation that divides
+// takes an explicit `Rounding` so callers decide who absorbs the remainder.
+
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+
+pub const WAD: u128 = 1_000_000_000_000_000_000;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Rounding {
+    Down,
+    Up,
+}
+
+// a * b / denominator, rounded in the requested direction
+pub fn mul_div(a: u128, b: u128, denominator: u128, rounding: Rounding) -> Result<u128> {
+    require!(denominator != 0, ErrorCode::DivideByZero);
+    let product = a.checked_mul(b).ok_or(ErrorCode::MathOverflow)?;
+    let quotient = product / denominator;
+    match rounding {
+        Rounding::Up if product % denominator != 0 => {
+            Ok(quotient.checked_add(1).ok_or(ErrorCode::MathOverflow)?)
+        }
+        _ => Ok(quotient),
+    }
+}
+
+pub fn wad_mul(a: u128, b: u128, rounding: Rounding) -> Result<u128> {
+    mul_div(a, b, WAD, rounding)
+}
+
+pub fn wad_div(a: u128, b: u128, rounding: Rounding) -> Result<u128> {
+    mul_div(a, WAD, b, rounding)
+}
+
+// Converts a pyth `price * 10^expo` into a WAD, rounding down.
+pub fn price_to_wad(price: i64, expo: i32) -> Result<u128> {
+    require!(price > 0, ErrorCode::InvalidPrice);
+    let price = price as u128;
+    let shift = 18i32.checked_add(expo).ok_or(ErrorCode::InvalidPrice)?;
+    let scale = 10u128
+        .checked_pow(shift.unsigned_abs())
+        .ok_or(ErrorCode::MathOverflow)?;
+    if shift >= 0 {
+        Ok(price.checked_mul(scale).ok_or(ErrorCode::MathOverflow)?)
+    } else {
+        Ok(price / scale)
+    }
+}
+
+// Moves `value` from `from_decimals` to `to_decimals` fixed-point places.
+pub fn rescale(value: u128, from_decimals: u8, to_decimals: u8, rounding: Rounding) -> Result<u128> {
+    if from_decimals == to_decimals {
+        return Ok(value);
+    }
+    let shift = from_decimals.abs_diff(to_decimals) as u32;
+    let scale = 10u128.checked_pow(shift).ok_or(ErrorCode::MathOverflow)?;
+    if to_decimals > from_decimals {
+        Ok(value.checked_mul(scale).ok_or(ErrorCode::MathOverflow)?)
+    } else {
+        mul_div(value, 1, scale, rounding)
+    }
+}
+
+pub fn to_u64(value: u128) -> Result<u64> {
+    Ok(u64::try_from(value).map_err(|_| ErrorCode::MathOverflow)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{auction_price, calculate_health_factor, liquidation_price, BASIS_POINTS};
+
+    #[test]
+    fn mul_div_rounds_in_the_requested_direction() {
+        assert_eq!(mul_div(10, 1, 3, Rounding::Down).unwrap(), 3);
+        assert_eq!(mul_div(10, 1, 3, Rounding::Up).unwrap(), 4);
+        // exact results are never bumped
+        assert_eq!(mul_div(9, 1, 3, Rounding::Up).unwrap(), 3);
+        assert_eq!(mul_div(0, 5, 3, Rounding::Up).unwrap(), 0);
+        assert_eq!(wad_mul(3 * WAD, WAD / 2, Rounding::Down).unwrap(), 3 * WAD / 2);
+        assert_eq!(wad_div(WAD, 3 * WAD, Rounding::Down).unwrap(), 333_333_333_333_333_333);
+        assert_eq!(wad_div(WAD, 3 * WAD, Rounding::Up).unwrap(), 333_333_333_333_333_334);
+    }
+
+    #[test]
+    fn mul_div_rejects_overflow_and_zero_denominators() {
+        assert!(mul_div(u128::MAX, 2, 2, Rounding::Down).is_err());
+        assert!(mul_div(1, 1, 0, Rounding::Down).is_err());
+        assert!(mul_div(u128::MAX, 1, 1, Rounding::Down).is_ok());
+    }
+
+    #[test]
+    fn rescale_moves_decimals_both_ways() {
+        assert_eq!(rescale(1_234_567, 6, 9, Rounding::Down).unwrap(), 1_234_567_000);
+        assert_eq!(rescale(1_234_567_891, 9, 6, Rounding::Down).unwrap(), 1_234_567);
+        assert_eq!(rescale(1_234_567_891, 9, 6, Rounding::Up).unwrap(), 1_234_568);
+        assert_eq!(rescale(42, 9, 9, Rounding::Up).unwrap(), 42);
+        assert!(rescale(u128::MAX, 0, 18, Rounding::Down).is_err());
+    }
+
+    #[test]
+    fn price_to_wad_handles_negative_and_positive_exponents() {
+        assert_eq!(price_to_wad(14_399_000_000, -8).unwrap(), 143_990_000_000_000_000_000);
+        assert_eq!(price_to_wad(2, 3).unwrap(), 2_000 * WAD);
+        assert!(price_to_wad(0, -8).is_err());
+        assert!(price_to_wad(-1, -8).is_err());
+    }
+
+    #[test]
+    fn to_u64_checks_bounds() {
+        assert_eq!(to_u64(u64::MAX as u128).unwrap(), u64::MAX);
+        assert!(to_u64(u64::MAX as u128 + 1).is_err());
+    }
+
+    #[test]
+    fn health_factor_without_debt_is_max() {
+        assert_eq!(calculate_health_factor(0, 1_000, 8000).unwrap(), u128::MAX);
+        assert_eq!(calculate_health_factor(0, 0, 8000).unwrap(), u128::MAX);
+    }
+
+    #[test]
+    fn health_factor_at_liquidation_threshold_is_one() {
+        // $100 of collateral at an 80% threshold supports exactly $80 of debt
+        assert_eq!(calculate_health_factor(80_000_000_000, 100_000_000_000, 8000).unwrap(), WAD);
+    }
+
+    #[test]
+    fn health_factor_just_below_threshold_is_below_one() {
+        // one base unit of extra debt must tip the position under 1.0
+        let health_factor = calculate_health_factor(80_000_000_001, 100_000_000_000, 8000).unwrap();
+        assert!(health_factor < WAD);
+        assert!(health_factor > WAD - WAD / 1_000_000_000);
+    }
+
+    #[test]
+    fn health_factor_keeps_fractions() {
+        // 1.99 and 1.0 used to both truncate to 1
+        let health_factor = calculate_health_factor(100_000_000_000, 199_000_000_000, BASIS_POINTS).unwrap();
+        assert_eq!(health_factor, 199 * WAD / 100);
+        assert!(health_factor > calculate_health_factor(100_000_000_000, 100_000_000_000, BASIS_POINTS).unwrap());
+    }
+
+    #[test]
+    fn health_factor_without_collateral_is_zero() {
+        assert_eq!(calculate_health_factor(1, 0, 8000).unwrap(), 0);
+    }
+
+    #[test]
+    fn health_factor_rounds_down() {
+        // 2/3 can not be represented exactly and must not be rounded up
+        let health_factor = calculate_health_factor(3, 2, BASIS_POINTS).unwrap();
+        assert_eq!(health_factor, 666_666_666_666_666_666);
+    }
+
+    #[test]
+    fn liquidation_price_is_where_health_factor_reaches_one() {
+        // 1 SOL backing $80 at an 80% threshold is liquidated at $100
+        let price = liquidation_price(80_000_000_000, 1_000_000_000, 8000).unwrap();
+        assert_eq!(price, 100 * WAD);
+        assert_eq!(liquidation_price(0, 1_000_000_000, 8000).unwrap(), 0);
+        assert_eq!(liquidation_price(1, 0, 8000).unwrap(), u128::MAX);
+    }
+
+    #[test]
+    fn auction_price_decays_to_the_floor() {
+        let start = 120 * WAD;
+        assert_eq!(auction_price(start, 0, 3600, 5000).unwrap(), start);
+        assert_eq!(auction_price(start, 1800, 3600, 5000).unwrap(), 90 * WAD);
+        assert_eq!(auction_price(start, 3600, 3600, 5000).unwrap(), 60 * WAD);
+        assert_eq!(auction_price(start, 7200, 3600, 5000).unwrap(), 60 * WAD);
+        assert!(auction_price(start, 1, 3600, 5000).unwrap() < start);
+    }
+}
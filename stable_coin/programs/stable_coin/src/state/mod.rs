@@ -1,19 +1,305 @@
 use anchor_lang::{prelude::*};
 
+use crate::{error::ErrorCode, math::{mul_div, to_u64, wad_div, wad_mul, Rounding, WAD}, BASIS_POINTS, MAX_POSITION_ASSETS, POOL_SCALE_FACTOR, REDEMPTION_BETA, REDEMPTION_HALF_LIFE};
+
 // account to store global info for our stable coin
 #[account]
 #[derive(InitSpace)]
 pub struct Config {
     pub authority:Pubkey,
+    // set by `propose_authority`, cleared once the new authority accepts
+    pub pending_authority:Option<Pubkey>,
+    // optional key that can pause instructions but never unpause them
+    pub guardian:Option<Pubkey>,
+    // bitfield of PAUSE_* flags, a set bit disables that instruction
+    pub paused_flags:u32,
     pub mint_address:Pubkey,
     pub liq_thx:u64,
     pub liq_bonus:u64,
+    // basis points, 10000 == a health factor of 1.0
     pub min_health_factor:u64,
     pub close_factor:u64,
+    // pyth feed the `PriceUpdateV2` account must carry
+    pub feed_id:[u8;32],
+    // max age of the price update in seconds
+    pub max_price_age:u64,
+    // max conf / price ratio in basis points
+    pub max_conf_bps:u64,
+    pub price_mode:PriceMode,
+    // stability fee charged on all debt, per second as a WAD (1e18 == 100%)
+    pub stability_fee_rate:u128,
+    // cumulative stability fee as a WAD, what a position owes is its
+    // normalized debt multiplied by this index
+    pub debt_index:u128,
+    // unix timestamp `debt_index` was last brought up to date
+    pub last_accrual:i64,
+    // basis points of the minted stablecoins paid to the treasury
+    pub mint_fee_bps:u64,
+    // basis points of the SOL returned by `withdraw_burn` paid to the treasury
+    pub redemption_fee_bps:u64,
+    // basis points of the repaid debt, in collateral, paid to the treasury on top of the liquidator's share
+    pub liquidation_fee_bps:u64,
+    // max stablecoins owed across the whole protocol, including accrued fees
+    pub global_debt_ceiling:u64,
+    // max stablecoins a single position can owe, including accrued fees
+    pub max_debt_per_position:u64,
+    // normalized debt of every position, see `debt_of`
+    pub total_debt:u64,
+    // auction start price as basis points of the oracle price
+    pub auction_buffer_bps:u64,
+    // seconds the auction price takes to decay to its floor
+    pub auction_duration:i64,
+    // lowest auction price as basis points of its start price
+    pub auction_floor_bps:u64,
+    // WAD rate added to `redemption_fee_bps` by `redeem`, grows with redeemed volume and decays over time
+    pub redemption_base_rate:u128,
+    // unix timestamp of the last `redeem`
+    pub last_redemption:i64,
+    // basis points of the stablecoins minted by `swap_in` paid to the treasury
+    pub psm_fee_in_bps:u64,
+    // basis points of the stablecoins sold to `swap_out` paid to the treasury
+    pub psm_fee_out_bps:u64,
+    // max stablecoins the PSM can have minted against its reserve
+    pub psm_debt_ceiling:u64,
+    // stablecoins minted by the PSM and not yet swapped back out
+    pub psm_debt:u64,
+    // stability fees accrued on the protocol debt and not yet minted into the treasury,
+    // collected by `withdraw_treasury` and drawn on by `settle_bad_debt` before the stability pool
+    pub surplus:u64,
     pub bump:u8,
     pub bump_mint_acc:u8,
 }
 
+impl Config {
+    pub fn is_paused(&self, flag:u32) -> bool {
+        self.paused_flags & flag != 0
+    }
+
+    // Gives up governance for good. Nobody could lift a pause afterwards, so this fails
+    // while any flag is set, and the guardian is removed so it cannot pause anything later.
+    pub fn renounce_authority(&mut self) -> Result<()> {
+        require!(self.paused_flags == 0, ErrorCode::RenounceWhilePaused);
+        self.authority = Pubkey::default();
+        self.pending_authority = None;
+        self.guardian = None;
+        Ok(())
+    }
+
+    // `min_health_factor` on the same WAD scale as `calculate_health_factor`
+    pub fn min_health_factor_wad(&self) -> Result<u128> {
+        mul_div(self.min_health_factor as u128, WAD, BASIS_POINTS as u128, Rounding::Up)
+    }
+
+    // Brings `debt_index` up to the current clock, must run before any debt is read or changed.
+    pub fn accrue_interest(&mut self) -> Result<()> {
+        self.accrue_interest_until(Clock::get()?.unix_timestamp)
+    }
+
+    // Grows `debt_index` by `stability_fee_rate * elapsed seconds`, so the fee
+    // compounds every time an instruction touches the config. What the protocol debt
+    // grew by is added to `surplus`.
+    pub fn accrue_interest_until(&mut self, now:i64) -> Result<()> {
+        let elapsed = now.saturating_sub(self.last_accrual);
+        if elapsed <= 0 {
+            return Ok(());
+        }
+        let growth = self.stability_fee_rate
+            .checked_mul(elapsed as u128)
+            .and_then(|v| v.checked_add(WAD))
+            .ok_or(ErrorCode::MathOverflow)?;
+        let debt_before = self.debt_of(self.total_debt)?;
+        self.debt_index = wad_mul(self.debt_index, growth, Rounding::Up)?;
+        self.last_accrual = now;
+
+        // the fee owed on top of the old debt is protocol revenue, booked as surplus
+        let accrued = self.debt_of(self.total_debt)?.saturating_sub(debt_before);
+        self.surplus = self.surplus.checked_add(accrued).ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    // Hands over the booked surplus to be minted into the treasury.
+    pub fn take_surplus(&mut self) -> u64 {
+        std::mem::take(&mut self.surplus)
+    }
+
+    // Stablecoins owed for `normalized_debt` at the current index, rounded up against the borrower.
+    pub fn debt_of(&self, normalized_debt:u64) -> Result<u64> {
+        to_u64(wad_mul(normalized_debt as u128, self.debt_index, Rounding::Up)?)
+    }
+
+    // Normalized debt worth `amount` stablecoins at the current index.
+    pub fn normalize(&self, amount:u64, rounding:Rounding) -> Result<u64> {
+        to_u64(wad_div(amount as u128, self.debt_index, rounding)?)
+    }
+
+    // Books `normalized_amount` of new debt on the protocol and checks the global and per
+    // position ceilings, `position_debt` is the normalized debt of the position after the mint.
+    pub fn add_debt(&mut self, normalized_amount:u64, position_debt:u64) -> Result<()> {
+        let total_debt = self.total_debt.checked_add(normalized_amount).ok_or(ErrorCode::MathOverflow)?;
+        require!(self.debt_of(total_debt)? <= self.global_debt_ceiling, ErrorCode::GlobalDebtCeilingExceeded);
+        require!(self.debt_of(position_debt)? <= self.max_debt_per_position, ErrorCode::PositionDebtCeilingExceeded);
+        self.total_debt = total_debt;
+        Ok(())
+    }
+
+    // Largest amount `add_debt` accepts on top of `position_debt` (normalized) for a position that
+    // may owe at most `max_debt` stablecoins. Works in normalized debt, so the amount still fits
+    // once `normalize` rounds it up at mint time.
+    pub fn max_mintable(&self, position_debt:u64, max_debt:u64) -> Result<u64> {
+        let max_debt = max_debt.min(self.max_debt_per_position);
+        // the largest normalized debt whose accrued value stays within each limit
+        let position_headroom = self.normalize(max_debt, Rounding::Down)?.saturating_sub(position_debt);
+        let global_headroom = self.normalize(self.global_debt_ceiling, Rounding::Down)?.saturating_sub(self.total_debt);
+        let headroom = position_headroom.min(global_headroom);
+        to_u64(wad_mul(headroom as u128, self.debt_index, Rounding::Down)?)
+    }
+
+    // Takes repaid or liquidated normalized debt off the protocol total.
+    pub fn remove_debt(&mut self, normalized_amount:u64) -> Result<()> {
+        self.total_debt = self.total_debt.checked_sub(normalized_amount).ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    // `redemption_base_rate` decayed up to `now`. Halves every `REDEMPTION_HALF_LIFE` seconds,
+    // decaying linearly between halvings.
+    pub fn decayed_redemption_rate(&self, now:i64) -> Result<u128> {
+        let elapsed = now.saturating_sub(self.last_redemption).max(0);
+        let halvings = elapsed / REDEMPTION_HALF_LIFE;
+        if halvings >= 128 {
+            return Ok(0);
+        }
+        let rate = self.redemption_base_rate >> halvings;
+        let partial = mul_div(rate, (elapsed % REDEMPTION_HALF_LIFE) as u128, 2 * REDEMPTION_HALF_LIFE as u128, Rounding::Down)?;
+        Ok(rate - partial)
+    }
+
+    // Raises the base rate for redeeming `amount` out of `supply` stablecoins and returns the
+    // WAD fee rate charged on it, `redemption_fee_bps` plus the base rate capped at 100%.
+    pub fn update_redemption_rate(&mut self, amount:u64, supply:u64, now:i64) -> Result<u128> {
+        let increase = mul_div(amount as u128, WAD, (supply as u128).checked_mul(REDEMPTION_BETA).ok_or(ErrorCode::MathOverflow)?, Rounding::Up)?;
+        self.redemption_base_rate = self.decayed_redemption_rate(now)?
+            .checked_add(increase).ok_or(ErrorCode::MathOverflow)?
+            .min(WAD);
+        self.last_redemption = now;
+        let floor = mul_div(self.redemption_fee_bps as u128, WAD, BASIS_POINTS as u128, Rounding::Up)?;
+        Ok(floor.saturating_add(self.redemption_base_rate).min(WAD))
+    }
+
+    // Books stablecoins minted by the PSM against its reserve, capped by `psm_debt_ceiling`.
+    pub fn add_psm_debt(&mut self, coins:u64) -> Result<()> {
+        let psm_debt = self.psm_debt.checked_add(coins).ok_or(ErrorCode::MathOverflow)?;
+        require!(psm_debt <= self.psm_debt_ceiling, ErrorCode::PsmDebtCeilingExceeded);
+        self.psm_debt = psm_debt;
+        Ok(())
+    }
+
+    pub fn remove_psm_debt(&mut self, coins:u64) -> Result<()> {
+        self.psm_debt = self.psm_debt.checked_sub(coins).ok_or(ErrorCode::InsufficientPsmReserve)?;
+        Ok(())
+    }
+
+    // Normalized debt cleared by repaying `amount` stablecoins of `normalized_debt`.
+    // Repaying the full accrued debt clears the whole position, partial repayments round down.
+    pub fn normalized_repayment(&self, normalized_debt:u64, amount:u64) -> Result<u64> {
+        let debt = self.debt_of(normalized_debt)?;
+        require!(amount <= debt, ErrorCode::RepayExceedsDebt);
+        if amount == debt {
+            return Ok(normalized_debt);
+        }
+        Ok(self.normalize(amount, Rounding::Down)?.min(normalized_debt))
+    }
+}
+
+// which pyth prices the health checks are run against
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PriceMode {
+    // spot price only
+    Spot,
+    // spot and EMA price, taking whichever is more conservative for the protocol in the
+    // operation: the lower when minting or withdrawing, the higher when liquidating or redeeming
+    SpotAndEma,
+}
+
+// protocol wide totals kept up to date by the user facing instructions, so
+// solvency can be read with a single account fetch
+#[account]
+#[derive(InitSpace)]
+pub struct ProtocolState {
+    // SOL held as collateral by every `Collateral` and `Position`
+    pub total_collateral_lamports:u64,
+    // stablecoins owed across the protocol, accrued up to the last update
+    pub total_debt:u64,
+    // open `Collateral`, `TokenCollateral` and `Position` accounts
+    pub position_count:u64,
+    // debt repaid by liquidators, in stablecoin base units
+    pub total_liquidated:u64,
+    // fees paid to the treasury in stablecoin base units, SOL fees valued at the price they were charged at
+    pub cumulative_fees:u64,
+    // debt written off by `settle_bad_debt` that neither the treasury nor the stability
+    // pool could cover, stablecoins in circulation that no collateral backs
+    pub bad_debt:u64,
+    pub bump:u8,
+}
+
+impl ProtocolState {
+    pub fn add_collateral(&mut self, lamports:u64) -> Result<()> {
+        self.total_collateral_lamports = self.total_collateral_lamports.checked_add(lamports).ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn remove_collateral(&mut self, lamports:u64) -> Result<()> {
+        self.total_collateral_lamports = self.total_collateral_lamports.checked_sub(lamports).ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn open_position(&mut self) -> Result<()> {
+        self.position_count = self.position_count.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn close_position(&mut self) -> Result<()> {
+        self.position_count = self.position_count.checked_sub(1).ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn record_liquidation(&mut self, coins:u64) -> Result<()> {
+        self.total_liquidated = self.total_liquidated.checked_add(coins).ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn record_fee(&mut self, coins:u64) -> Result<()> {
+        self.cumulative_fees = self.cumulative_fees.checked_add(coins).ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn record_bad_debt(&mut self, coins:u64) -> Result<()> {
+        self.bad_debt = self.bad_debt.checked_add(coins).ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn cover_bad_debt(&mut self, coins:u64) -> Result<()> {
+        self.bad_debt = self.bad_debt.checked_sub(coins).ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    // Copies the accrued protocol debt from the config, run after `Config.total_debt` or the index moved.
+    pub fn sync_debt(&mut self, config:&Config) -> Result<()> {
+        self.total_debt = config.debt_of(config.total_debt)?;
+        Ok(())
+    }
+}
+
+// protocol revenue, SOL fees are held as lamports on this account and
+// stablecoin fees in `token_account`, both paid out by `withdraw_treasury`.
+// SPL collateral fees sit in one `treasury_collateral` account per mint, paid out by `withdraw_treasury_collateral`
+#[account]
+#[derive(InitSpace)]
+pub struct Treasury {
+    pub token_account:Pubkey,
+    pub bump:u8,
+    pub bump_token_account:u8,
+}
+
 // collteral account for each user to store collateral and stable coin info.
 #[account]
 #[derive(InitSpace)]
@@ -23,7 +309,522 @@ pub struct Collateral {
     pub coin_token_account:Pubkey,
     pub is_initialized:bool,
     pub lamports:u64,
+    // normalized debt, see `Config.debt_of`
     pub coins:u64,
+    // set while an `Auction` is selling this position's collateral
+    pub in_auction:bool,
     pub bump:u8,
     pub bump_sol_account:u8
-}
\ No newline at end of file
+}
+
+// Dutch auction of an unhealthy `Collateral` position, created by `start_auction`. The
+// position's collateral is sold for stablecoins at a price that decays from `start_price`
+// until the debt is covered, whatever collateral is left then goes back to the borrower.
+// A `repay` that makes the position healthy again ends the auction early.
+#[account]
+#[derive(InitSpace)]
+pub struct Auction {
+    // the `Collateral` account being auctioned
+    pub position:Pubkey,
+    // depositer of the position, receives the surplus collateral
+    pub owner:Pubkey,
+    // caller of `start_auction`, receives the account rent once the auction settles
+    pub keeper:Pubkey,
+    // WAD price of SOL the auction opened at
+    pub start_price:u128,
+    pub started_at:i64,
+    pub bump:u8,
+}
+// risk settings for one SPL collateral mint, created by governance through `add_collateral_type`
+#[account]
+#[derive(InitSpace)]
+pub struct CollateralType {
+    pub mint:Pubkey,
+    // program owned token account holding every deposit of this mint
+    pub vault:Pubkey,
+    pub feed_id:[u8;32],
+    pub decimals:u8,
+    // basis points, same meaning as on `Config`
+    pub liq_thx:u64,
+    pub liq_bonus:u64,
+    // max stablecoins that can be minted against this mint
+    pub debt_ceiling:u64,
+    // normalized debt of every position on this collateral type
+    pub total_debt:u64,
+    pub bump:u8,
+    pub bump_vault:u8,
+}
+
+// position of one user against one collateral type, keyed by (depositer, collateral type)
+#[account]
+#[derive(InitSpace)]
+pub struct TokenCollateral {
+    pub depositer:Pubkey,
+    pub collateral_type:Pubkey,
+    pub is_initialized:bool,
+    // deposited collateral in base units of the collateral mint
+    pub amount:u64,
+    // normalized debt, see `Config.debt_of`
+    pub coins:u64,
+    pub bump:u8,
+}
+
+// one SPL collateral balance held by a `Position`
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy)]
+pub struct PositionBalance {
+    pub collateral_type:Pubkey,
+    // base units of the collateral type's mint, held in its vault
+    pub amount:u64,
+    // share of the position's normalized debt counted in the collateral type's `total_debt`
+    pub debt:u64,
+}
+
+// cross-collateral position: native SOL plus up to `MAX_POSITION_ASSETS` SPL collateral
+// types, all backing a single debt and a single health factor. The debt is split across
+// the SPL balances by the weighted value they add, so each collateral type's debt ceiling
+// also covers what it backs inside positions.
+#[account]
+#[derive(InitSpace)]
+pub struct Position {
+    pub owner:Pubkey,
+    // normalized debt, see `Config.debt_of`
+    pub coins:u64,
+    // SOL held in the position's own vault PDA
+    pub lamports:u64,
+    #[max_len(MAX_POSITION_ASSETS)]
+    pub balances:Vec<PositionBalance>,
+    pub bump:u8,
+    pub bump_sol_account:u8,
+}
+
+impl Position {
+    pub fn balance_index(&self, collateral_type:&Pubkey) -> Option<usize> {
+        self.balances.iter().position(|b| b.collateral_type == *collateral_type)
+    }
+}
+
+// reward sum of the stability pool for one (epoch, scale) pair
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy)]
+pub struct PoolSum {
+    pub epoch:u64,
+    pub scale:u64,
+    pub sum:u128,
+}
+
+// Liquity style stability pool. Stablecoin deposits are burned to repay unhealthy positions
+// and the seized SOL is shared among depositors pro-rata without touching every deposit:
+// `p` tracks how much of a deposit survives the liquidations so far and the sums track the
+// SOL earned per unit of deposit, both compared against the snapshot on each `PoolDeposit`.
+// SOL gains are held as lamports on this account, deposits in `token_account`.
+#[account]
+#[derive(InitSpace)]
+pub struct StabilityPool {
+    pub token_account:Pubkey,
+    // stablecoins left in the pool after liquidations
+    pub total_deposits:u64,
+    // running product as a WAD, every liquidation scales it by the share of deposits left
+    pub p:u128,
+    // bumped each time a liquidation empties the pool, resetting `p`
+    pub epoch:u64,
+    // bumped each time `p` is rescaled by `POOL_SCALE_FACTOR` to keep its precision
+    pub scale:u64,
+    // lamports earned per unit of deposit, scaled by `p`, one entry per (epoch, scale) in
+    // increasing order. Never pruned, a deposit snapshotted at any (epoch, scale) can still
+    // claim from it, the account grows through `realloc` in `liquidate_with_pool` instead
+    #[max_len(0)]
+    pub sums:Vec<PoolSum>,
+    pub bump:u8,
+    pub bump_token_account:u8,
+}
+
+impl StabilityPool {
+    // Empty pool at the first epoch and scale.
+    pub fn new(token_account:Pubkey, bump:u8, bump_token_account:u8) -> Self {
+        StabilityPool {
+            token_account,
+            total_deposits: 0,
+            p: WAD,
+            epoch: 0,
+            scale: 0,
+            sums: Vec::new(),
+            bump,
+            bump_token_account,
+        }
+    }
+
+    // Account size holding `sums` reward sums.
+    pub fn space(sums:usize) -> usize {
+        8 + StabilityPool::INIT_SPACE + sums * PoolSum::INIT_SPACE
+    }
+
+    // Reward sums held once the next liquidation has been offset, one more if it opens the
+    // sum of the current (epoch, scale).
+    pub fn sums_after_offset(&self) -> usize {
+        match self.sums.last() {
+            Some(last) if last.epoch == self.epoch && last.scale == self.scale => self.sums.len(),
+            _ => self.sums.len() + 1,
+        }
+    }
+
+    pub fn sum_at(&self, epoch:u64, scale:u64) -> u128 {
+        self.sums.binary_search_by_key(&(epoch, scale), |s| (s.epoch, s.scale))
+            .map_or(0, |i| self.sums[i].sum)
+    }
+
+    fn add_to_sum(&mut self, increment:u128) -> Result<()> {
+        // a missing sum reads as zero, so nothing is stored until there is a gain
+        if increment == 0 {
+            return Ok(());
+        }
+        let (epoch, scale) = (self.epoch, self.scale);
+        match self.sums.last_mut() {
+            Some(last) if last.epoch == epoch && last.scale == scale => {
+                last.sum = last.sum.checked_add(increment).ok_or(ErrorCode::MathOverflow)?;
+            }
+            _ => self.sums.push(PoolSum { epoch, scale, sum: increment }),
+        }
+        Ok(())
+    }
+
+    // Burns `debt` of the pooled stablecoins against a liquidated position and shares the
+    // `lamports` seized from it among the depositors.
+    pub fn offset(&mut self, debt:u64, lamports:u64) -> Result<()> {
+        require!(debt > 0 && debt <= self.total_deposits, ErrorCode::InsufficientPoolDeposits);
+        let total_deposits = self.total_deposits as u128;
+
+        // SOL per unit of deposit, rounded down so the pool never pays out more than it holds
+        let gain_per_unit = mul_div(lamports as u128, WAD, total_deposits, Rounding::Down)?;
+        self.add_to_sum(wad_mul(gain_per_unit, self.p, Rounding::Down)?)?;
+
+        // share of every deposit that survives, the loss rounds up against the depositors
+        let product_factor = if debt == self.total_deposits {
+            0
+        } else {
+            WAD - mul_div(debt as u128, WAD, total_deposits, Rounding::Up)?
+        };
+
+        if product_factor == 0 {
+            self.epoch = self.epoch.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+            self.scale = 0;
+            self.p = WAD;
+        } else {
+            let product = self.p.checked_mul(product_factor).ok_or(ErrorCode::MathOverflow)?;
+            if product / WAD < POOL_SCALE_FACTOR {
+                self.p = mul_div(product, POOL_SCALE_FACTOR, WAD, Rounding::Down)?;
+                self.scale = self.scale.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+            } else {
+                self.p = product / WAD;
+            }
+            require!(self.p > 0, ErrorCode::MathOverflow);
+        }
+
+        self.total_deposits -= debt;
+        Ok(())
+    }
+
+    // What is left of `deposit` after the liquidations since its snapshot.
+    pub fn compounded_deposit(&self, deposit:&PoolDeposit) -> Result<u64> {
+        if deposit.amount == 0 || deposit.epoch < self.epoch {
+            return Ok(0);
+        }
+        let compounded = match self.scale - deposit.scale {
+            0 => mul_div(deposit.amount as u128, self.p, deposit.p, Rounding::Down)?,
+            1 => mul_div(deposit.amount as u128, self.p, deposit.p, Rounding::Down)? / POOL_SCALE_FACTOR,
+            _ => 0,
+        };
+        // below this the product has lost too much precision to be trusted
+        if compounded < deposit.amount as u128 / POOL_SCALE_FACTOR {
+            return Ok(0);
+        }
+        to_u64(compounded)
+    }
+
+    // Lamports `deposit` earned from the liquidations since its snapshot. Gains made after
+    // the product moved to the next scale are counted at that scale's precision.
+    pub fn collateral_gain(&self, deposit:&PoolDeposit) -> Result<u64> {
+        if deposit.amount == 0 {
+            return Ok(0);
+        }
+        let first = self.sum_at(deposit.epoch, deposit.scale).saturating_sub(deposit.s);
+        let second = self.sum_at(deposit.epoch, deposit.scale + 1) / POOL_SCALE_FACTOR;
+        let gain = first.checked_add(second).ok_or(ErrorCode::MathOverflow)?;
+        to_u64(mul_div(deposit.amount as u128, gain, deposit.p, Rounding::Down)?)
+    }
+}
+
+// one depositor's share of the stability pool, valued against the pool snapshot taken when
+// it last changed
+#[account]
+#[derive(InitSpace)]
+pub struct PoolDeposit {
+    pub depositor:Pubkey,
+    // stablecoins deposited as of the snapshot
+    pub amount:u64,
+    // pool `p`, reward sum, epoch and scale when `amount` was set
+    pub p:u128,
+    pub s:u128,
+    pub epoch:u64,
+    pub scale:u64,
+    pub bump:u8,
+}
+
+impl PoolDeposit {
+    // Sets the deposit to `amount` and snapshots the pool it is measured against from now on.
+    pub fn snapshot(&mut self, amount:u64, pool:&StabilityPool) {
+        self.amount = amount;
+        self.p = pool.p;
+        self.s = pool.sum_at(pool.epoch, pool.scale);
+        self.epoch = pool.epoch;
+        self.scale = pool.scale;
+    }
+}
+
+// peg stability module, swaps `reserve_mint` 1:1 for the stablecoin and holds what it
+// receives in `vault`. Fees and the debt ceiling live on `Config`.
+#[account]
+#[derive(InitSpace)]
+pub struct Psm {
+    // USDC or any other stable mint the module is backed by
+    pub reserve_mint:Pubkey,
+    pub vault:Pubkey,
+    pub decimals:u8,
+    pub bump:u8,
+    pub bump_vault:u8,
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::{DEFAULT_AUCTION_BUFFER_BPS, DEFAULT_AUCTION_DURATION, DEFAULT_AUCTION_FLOOR_BPS, MAX_STABILITY_FEE_RATE, PAUSE_LIQUIDATE, SECONDS_PER_YEAR};
+
+    pub(crate) fn test_config() -> Config {
+        Config {
+            authority: Pubkey::default(),
+            pending_authority: None,
+            guardian: None,
+            paused_flags: 0,
+            mint_address: Pubkey::default(),
+            liq_thx: 8000,
+            liq_bonus: 500,
+            min_health_factor: 12_500,
+            close_factor: 5000,
+            feed_id: [0; 32],
+            max_price_age: 60,
+            max_conf_bps: 200,
+            price_mode: PriceMode::Spot,
+            stability_fee_rate: 0,
+            debt_index: WAD,
+            last_accrual: 0,
+            mint_fee_bps: 0,
+            redemption_fee_bps: 0,
+            liquidation_fee_bps: 0,
+            global_debt_ceiling: u64::MAX,
+            max_debt_per_position: u64::MAX,
+            total_debt: 0,
+            auction_buffer_bps: DEFAULT_AUCTION_BUFFER_BPS,
+            auction_duration: DEFAULT_AUCTION_DURATION,
+            auction_floor_bps: DEFAULT_AUCTION_FLOOR_BPS,
+            redemption_base_rate: 0,
+            last_redemption: 0,
+            psm_fee_in_bps: 0,
+            psm_fee_out_bps: 0,
+            psm_debt_ceiling: 0,
+            psm_debt: 0,
+            surplus: 0,
+            bump: 0,
+            bump_mint_acc: 0,
+        }
+    }
+
+    #[test]
+    fn debt_grows_with_the_stability_fee() {
+        let mut config = test_config();
+        config.stability_fee_rate = MAX_STABILITY_FEE_RATE / 10;
+        let normalized = config.normalize(100_000_000_000, Rounding::Up).unwrap();
+
+        // a year of 10% simple interest in one accrual
+        config.accrue_interest_until(SECONDS_PER_YEAR as i64).unwrap();
+        let debt = config.debt_of(normalized).unwrap();
+        // the per second rate is truncated, so allow a couple of base units of drift
+        assert!(debt.abs_diff(110_000_000_000) <= 2);
+
+        // a clock that went backwards leaves the index alone
+        let index = config.debt_index;
+        config.accrue_interest_until(0).unwrap();
+        assert_eq!(config.debt_index, index);
+    }
+
+    #[test]
+    fn stability_fee_is_booked_as_surplus() {
+        let mut config = test_config();
+        config.stability_fee_rate = MAX_STABILITY_FEE_RATE / 10;
+        config.total_debt = config.normalize(100_000_000_000, Rounding::Up).unwrap();
+
+        config.accrue_interest_until(SECONDS_PER_YEAR as i64 / 2).unwrap();
+        config.accrue_interest_until(SECONDS_PER_YEAR as i64).unwrap();
+        // the surplus is what the protocol debt grew by, minted once and then cleared
+        let debt = config.debt_of(config.total_debt).unwrap();
+        assert_eq!(config.surplus, debt - 100_000_000_000);
+        assert!(config.surplus.abs_diff(10_250_000_000) <= 4);
+        assert_eq!(config.take_surplus(), debt - 100_000_000_000);
+        assert_eq!(config.surplus, 0);
+    }
+
+    #[test]
+    fn debt_ceilings_reject_mints_past_the_cap() {
+        let mut config = test_config();
+        config.global_debt_ceiling = 1_000;
+        config.max_debt_per_position = 600;
+
+        assert!(config.add_debt(600, 600).is_ok());
+        assert!(config.add_debt(1, 601).is_err());
+        assert!(config.add_debt(401, 401).is_err());
+        assert!(config.add_debt(400, 400).is_ok());
+        assert_eq!(config.total_debt, 1_000);
+
+        config.remove_debt(600).unwrap();
+        assert_eq!(config.total_debt, 400);
+    }
+
+    #[test]
+    fn redemption_rate_rises_with_volume_and_decays() {
+        let mut config = test_config();
+        config.redemption_fee_bps = 50;
+
+        // redeeming 10% of the supply adds 5% on top of the 0.5% floor
+        let fee_rate = config.update_redemption_rate(100, 1000, 0).unwrap();
+        assert_eq!(config.redemption_base_rate, WAD / 20);
+        assert_eq!(fee_rate, WAD / 20 + WAD / 200);
+
+        assert_eq!(config.decayed_redemption_rate(REDEMPTION_HALF_LIFE).unwrap(), WAD / 40);
+        assert_eq!(config.decayed_redemption_rate(2 * REDEMPTION_HALF_LIFE).unwrap(), WAD / 80);
+        let halfway = config.decayed_redemption_rate(REDEMPTION_HALF_LIFE / 2).unwrap();
+        assert!(halfway < WAD / 20 && halfway > WAD / 40);
+
+        // a later redemption starts from the decayed rate
+        let fee_rate = config.update_redemption_rate(100, 1000, REDEMPTION_HALF_LIFE).unwrap();
+        assert_eq!(config.redemption_base_rate, WAD / 40 + WAD / 20);
+        assert_eq!(fee_rate, WAD / 40 + WAD / 20 + WAD / 200);
+    }
+
+    #[test]
+    fn psm_debt_is_capped_by_its_ceiling() {
+        let mut config = test_config();
+        assert!(config.add_psm_debt(1).is_err());
+
+        config.psm_debt_ceiling = 1_000_000_000;
+        config.add_psm_debt(600_000_000).unwrap();
+        assert!(config.add_psm_debt(400_000_001).is_err());
+        assert_eq!(config.psm_debt, 600_000_000);
+        config.add_psm_debt(400_000_000).unwrap();
+
+        config.remove_psm_debt(1_000_000_000).unwrap();
+        assert!(config.remove_psm_debt(1).is_err());
+    }
+
+    #[test]
+    fn renouncing_removes_the_guardian_and_requires_no_pauses() {
+        let mut config = test_config();
+        config.authority = Pubkey::new_unique();
+        config.guardian = Some(Pubkey::new_unique());
+        config.paused_flags = PAUSE_LIQUIDATE;
+        assert!(config.renounce_authority().is_err());
+        assert_ne!(config.authority, Pubkey::default());
+
+        config.paused_flags = 0;
+        config.renounce_authority().unwrap();
+        assert_eq!(config.authority, Pubkey::default());
+        assert_eq!(config.pending_authority, None);
+        assert_eq!(config.guardian, None);
+    }
+
+    #[test]
+    fn full_repayment_clears_normalized_debt() {
+        let mut config = test_config();
+        config.debt_index = WAD + WAD / 3;
+        let normalized = 75_000_000_000;
+        let debt = config.debt_of(normalized).unwrap();
+
+        assert_eq!(config.normalized_repayment(normalized, debt).unwrap(), normalized);
+        assert!(config.normalized_repayment(normalized, debt - 1).unwrap() < normalized);
+        assert!(config.normalized_repayment(normalized, debt + 1).is_err());
+    }
+
+    fn pool_deposit(amount:u64, pool:&StabilityPool) -> PoolDeposit {
+        let mut deposit = PoolDeposit { depositor: Pubkey::default(), amount: 0, p: 0, s: 0, epoch: 0, scale: 0, bump: 0 };
+        deposit.snapshot(amount, pool);
+        deposit
+    }
+
+    #[test]
+    fn stability_pool_shares_liquidations_pro_rata() {
+        let mut pool = StabilityPool::new(Pubkey::default(), 0, 0);
+        pool.total_deposits = 400_000_000_000;
+        let small = pool_deposit(100_000_000_000, &pool);
+        let large = pool_deposit(300_000_000_000, &pool);
+
+        // half of the pool repays debt for 2 SOL of collateral
+        pool.offset(200_000_000_000, 2_000_000_000).unwrap();
+        assert_eq!(pool.total_deposits, 200_000_000_000);
+        assert_eq!(pool.compounded_deposit(&small).unwrap(), 50_000_000_000);
+        assert_eq!(pool.compounded_deposit(&large).unwrap(), 150_000_000_000);
+        assert_eq!(pool.collateral_gain(&small).unwrap(), 500_000_000);
+        assert_eq!(pool.collateral_gain(&large).unwrap(), 1_500_000_000);
+
+        // a deposit made after the liquidation earns nothing from it
+        let late = pool_deposit(200_000_000_000, &pool);
+        pool.total_deposits += 200_000_000_000;
+        assert_eq!(pool.collateral_gain(&late).unwrap(), 0);
+
+        // emptying the pool starts a new epoch, earlier gains stay claimable
+        pool.offset(400_000_000_000, 1_000_000_000).unwrap();
+        assert_eq!(pool.epoch, 1);
+        assert_eq!(pool.total_deposits, 0);
+        assert_eq!(pool.compounded_deposit(&small).unwrap(), 0);
+        assert_eq!(pool.compounded_deposit(&late).unwrap(), 0);
+        assert_eq!(pool.collateral_gain(&late).unwrap(), 500_000_000);
+        assert_eq!(pool.collateral_gain(&small).unwrap(), 500_000_000 + 125_000_000);
+        assert!(pool.offset(1, 1).is_err());
+    }
+
+    #[test]
+    fn stability_pool_keeps_old_gains_across_many_epochs_and_scales() {
+        let mut pool = StabilityPool::new(Pubkey::default(), 0, 0);
+        pool.total_deposits = 1_000_000_000_000;
+        let first = pool_deposit(1_000_000_000_000, &pool);
+
+        // the first depositor takes a liquidation, the next one empties the pool
+        pool.offset(500_000_000_000, 3_000_000_000).unwrap();
+        pool.offset(500_000_000_000, 1_000_000_000).unwrap();
+        assert_eq!(pool.collateral_gain(&first).unwrap(), 4_000_000_000);
+
+        // 20 more epochs, each with a liquidation that also moves the pool to the next scale
+        for _ in 0..20 {
+            let epoch = pool.epoch;
+            pool.total_deposits = 1_000_000_000_000;
+            let later = pool_deposit(1_000_000_000_000, &pool);
+            pool.offset(999_999_999_900, 2_000_000_000).unwrap();
+            assert_eq!(pool.scale, 1);
+            pool.offset(100, 1_000).unwrap();
+            assert_eq!(pool.epoch, epoch + 1);
+            assert!(pool.collateral_gain(&later).unwrap() >= 2_000_000_000);
+        }
+        assert_eq!(pool.sums.len(), 41);
+        assert_eq!(StabilityPool::space(pool.sums.len()), 8 + StabilityPool::INIT_SPACE + 41 * 32);
+
+        // the first depositor can still claim everything it earned
+        assert_eq!(pool.collateral_gain(&first).unwrap(), 4_000_000_000);
+        assert_eq!(pool.compounded_deposit(&first).unwrap(), 0);
+    }
+
+    #[test]
+    fn socialized_bad_debt_cuts_pool_deposits_without_gains() {
+        let mut pool = StabilityPool::new(Pubkey::default(), 0, 0);
+        pool.total_deposits = 400_000_000_000;
+        let deposit = pool_deposit(100_000_000_000, &pool);
+
+        pool.offset(100_000_000_000, 0).unwrap();
+        assert_eq!(pool.compounded_deposit(&deposit).unwrap(), 75_000_000_000);
+        assert_eq!(pool.collateral_gain(&deposit).unwrap(), 0);
+    }
+}
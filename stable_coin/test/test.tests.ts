@@ -341,19 +341,37 @@ describe("testing pyth", () => {
         new PublicKey(IDL.address)
       );
 
+      const [protocolState] = PublicKey.findProgramAddressSync(
+        [Buffer.from("protocol_state")],
+        new PublicKey(IDL.address)
+      );
+      const [treasury] = PublicKey.findProgramAddressSync(
+        [Buffer.from("treasury")],
+        new PublicKey(IDL.address)
+      );
+      const [treasuryTokenAccount] = PublicKey.findProgramAddressSync(
+        [Buffer.from("treasury_token_account")],
+        new PublicKey(IDL.address)
+      );
+      // every instruction emits its events through a self-CPI
+      const [eventAuthority] = PublicKey.findProgramAddressSync(
+        [Buffer.from("__event_authority")],
+        new PublicKey(IDL.address)
+      );
+
       const bx = await connection.getLatestBlockhash();
       let Configix = await program.methods
         .processConfig(
-          signer.publicKey,
-          new PublicKey(MINT),
           new BN(8000), // liq thx
           new BN(500), // bonus
-          new BN(1500), // health factor
-          333, // bump mint
-          new BN(1) // close factor
+          new BN(15000), // min health factor, in bps and at least 10000
+          new BN(5000) // close factor
         )
         .accounts({
           admin: signer.publicKey,
+          protocolState,
+          eventAuthority,
+          program: new PublicKey(IDL.address),
         })
         .instruction();
 
@@ -383,6 +401,10 @@ describe("testing pyth", () => {
           mint: MINT,
           tokenProgram2022: TOKEN_2022_PROGRAM_ID,
           depositer: signer.publicKey,
+          protocolState,
+          treasuryTokenAccount,
+          eventAuthority,
+          program: new PublicKey(IDL.address),
         })
         .instruction();
       const tx = new Transaction({
@@ -405,7 +427,12 @@ describe("testing pyth", () => {
           priceUpdate: SOL_TO_USDC_ACCOUNT,
           mint: MINT,
           tokenProgram: TOKEN_2022_PROGRAM_ID,
-          withdrawer,
+          withdrawer: signer.publicKey,
+          config: getConfigAccount,
+          protocolState,
+          treasury,
+          eventAuthority,
+          program: new PublicKey(IDL.address),
         })
         .instruction();
 